@@ -0,0 +1,39 @@
+use bevy::{prelude::*, window::PrimaryWindow};
+
+pub struct CursorPlugin;
+
+impl Plugin for CursorPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<CursorPosition>()
+            .add_systems(PreUpdate, track_cursor_position);
+    }
+}
+
+/// Last known cursor position in the primary window, `world` is where it
+/// points at through the active 2D camera.
+#[derive(Resource, Default, Clone, Copy, PartialEq)]
+pub struct CursorPosition {
+    pub viewport: Vec2,
+    pub world: Vec2,
+}
+
+fn track_cursor_position(
+    window: Query<&Window, With<PrimaryWindow>>,
+    camera: Query<(&Camera, &GlobalTransform), With<Camera2d>>,
+    mut cursor_position: ResMut<CursorPosition>,
+) {
+    let Some(viewport) = window
+        .get_single()
+        .ok()
+        .and_then(|window| window.cursor_position())
+    else {
+        return;
+    };
+    // The world position changes with the camera even if the cursor doesn't
+    let world = camera
+        .iter()
+        .find(|(camera, _)| camera.is_active)
+        .and_then(|(camera, transform)| camera.viewport_to_world_2d(transform, viewport).ok())
+        .unwrap_or(cursor_position.world);
+    cursor_position.set_if_neq(CursorPosition { viewport, world });
+}
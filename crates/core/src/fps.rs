@@ -0,0 +1,68 @@
+use std::time::Duration;
+
+use bevy::{
+    diagnostic::{DiagnosticsStore, FrameTimeDiagnosticsPlugin},
+    prelude::*,
+    time::common_conditions::on_timer,
+};
+use playground_ui::{KeyBindingsAppExt, action_just_pressed};
+
+pub struct FpsPlugin;
+
+impl Plugin for FpsPlugin {
+    fn build(&self, app: &mut App) {
+        if !app.is_plugin_added::<FrameTimeDiagnosticsPlugin>() {
+            app.add_plugins(FrameTimeDiagnosticsPlugin);
+        }
+        app.register_action("toggle_fps", [KeyCode::F3])
+            .add_systems(Startup, spawn_fps_counter)
+            .add_systems(
+                Update,
+                (
+                    update_fps_counter.run_if(on_timer(Duration::from_millis(250))),
+                    toggle_fps_counter.run_if(action_just_pressed("toggle_fps")),
+                ),
+            );
+    }
+}
+
+/// Frames per second in the top right corner, toggled with F3.
+#[derive(Component)]
+pub struct FpsCounter;
+
+fn spawn_fps_counter(mut cmd: Commands) {
+    cmd.spawn((
+        FpsCounter,
+        Text::new("fps"),
+        TextFont::from_font_size(11.),
+        Node {
+            position_type: PositionType::Absolute,
+            top: Val::Px(2.),
+            right: Val::Px(4.),
+            ..default()
+        },
+        GlobalZIndex(i32::MAX - 1),
+        PickingBehavior::IGNORE,
+    ));
+}
+
+fn update_fps_counter(
+    diagnostics: Res<DiagnosticsStore>,
+    mut counter: Query<&mut Text, With<FpsCounter>>,
+) {
+    let Some(fps) = diagnostics
+        .get(&FrameTimeDiagnosticsPlugin::FPS)
+        .and_then(|fps| fps.smoothed())
+    else {
+        return;
+    };
+    for mut text in counter.iter_mut() {
+        text.0 = format!("{:.0} fps", fps);
+    }
+}
+
+fn toggle_fps_counter(mut counter: Query<&mut Visibility, With<FpsCounter>>) {
+    for mut visibility in counter.iter_mut() {
+        visibility.toggle_visible_hidden();
+    }
+}
@@ -0,0 +1,191 @@
+use std::{env, time::Duration};
+
+use bevy::{
+    app::ScheduleRunnerPlugin,
+    log::LogPlugin,
+    prelude::*,
+    render::{RenderPlugin, settings::WgpuSettings},
+    window::ExitCondition,
+    winit::WinitPlugin,
+};
+use playground_ui::{ActionInput, KeyBindingsAppExt, debug_log_layer, no_input_focused};
+
+mod cursor;
+mod fps;
+mod screenshot;
+
+pub use cursor::CursorPosition;
+pub use fps::FpsCounter;
+
+/// Window, logging, quitting and other boilerplate shared by the
+/// experiments, adds `DefaultPlugins` itself. Settings can be overridden from
+/// the command line with `--width 1280 --height 720 --title Demo --headless
+/// --no-fps`.
+pub struct PlaygroundAppPlugin {
+    pub settings: AppSettings,
+    pub args: bool,
+}
+
+impl PlaygroundAppPlugin {
+    pub fn new(title: impl Into<String>) -> Self {
+        Self {
+            settings: AppSettings {
+                title: title.into(),
+                ..default()
+            },
+            args: true,
+        }
+    }
+
+    pub fn with_size(mut self, width: f32, height: f32) -> Self {
+        self.settings.width = width;
+        self.settings.height = height;
+        self
+    }
+
+    pub fn with_quit_keys(mut self, keys: impl IntoIterator<Item = KeyCode>) -> Self {
+        self.settings.quit_keys = keys.into_iter().collect();
+        self
+    }
+
+    pub fn with_fps_counter(mut self, fps_counter: bool) -> Self {
+        self.settings.fps_counter = fps_counter;
+        self
+    }
+
+    pub fn headless(mut self) -> Self {
+        self.settings.headless = true;
+        self
+    }
+
+    /// Ignores the command line, e.g. in tests where it holds the test
+    /// filter.
+    pub fn without_args(mut self) -> Self {
+        self.args = false;
+        self
+    }
+}
+
+#[derive(Resource, Clone, Debug)]
+pub struct AppSettings {
+    pub title: String,
+    pub width: f32,
+    pub height: f32,
+    /// No window and no renderer, for tests and CI.
+    pub headless: bool,
+    /// Default bindings of the "quit" action.
+    pub quit_keys: Vec<KeyCode>,
+    pub fps_counter: bool,
+}
+
+impl Default for AppSettings {
+    fn default() -> Self {
+        Self {
+            title: "Bevy game".to_string(),
+            width: 900.,
+            height: 600.,
+            headless: false,
+            quit_keys: vec![KeyCode::KeyQ],
+            fps_counter: true,
+        }
+    }
+}
+
+impl AppSettings {
+    /// Applies the flags it knows, other arguments are left for the app.
+    pub fn apply_args(&mut self, args: impl IntoIterator<Item = String>) {
+        let mut args = args.into_iter();
+        while let Some(arg) = args.next() {
+            let (flag, value) = match arg.split_once('=') {
+                Some((flag, value)) => (flag.to_string(), Some(value.to_string())),
+                None => (arg, None),
+            };
+            match flag.as_str() {
+                "--headless" => self.headless = true,
+                "--no-fps" => self.fps_counter = false,
+                "--width" | "--height" | "--title" => {
+                    let Some(value) = value.or_else(|| args.next()) else {
+                        warn!("Missing value for {}", flag);
+                        continue;
+                    };
+                    match flag.as_str() {
+                        "--title" => self.title = value,
+                        _ => match value.parse::<f32>() {
+                            Ok(size) if flag == "--width" => self.width = size,
+                            Ok(size) => self.height = size,
+                            Err(err) => warn!("Invalid {} '{}': {}", flag, value, err),
+                        },
+                    }
+                }
+                _ => {}
+            }
+        }
+    }
+}
+
+impl Plugin for PlaygroundAppPlugin {
+    fn build(&self, app: &mut App) {
+        let mut settings = self.settings.clone();
+        if self.args {
+            settings.apply_args(env::args().skip(1));
+        }
+
+        let log = LogPlugin {
+            custom_layer: debug_log_layer,
+            ..default()
+        };
+        if settings.headless {
+            app.add_plugins((
+                DefaultPlugins
+                    .set(WindowPlugin {
+                        primary_window: None,
+                        exit_condition: ExitCondition::DontExit,
+                        close_when_requested: false,
+                    })
+                    .set(RenderPlugin {
+                        render_creation: WgpuSettings {
+                            backends: None,
+                            ..default()
+                        }
+                        .into(),
+                        ..default()
+                    })
+                    .set(log)
+                    .disable::<WinitPlugin>(),
+                ScheduleRunnerPlugin::run_loop(Duration::from_secs_f64(1. / 60.)),
+            ));
+        } else {
+            app.add_plugins((
+                DefaultPlugins
+                    .set(WindowPlugin {
+                        primary_window: Some(Window {
+                            resolution: (settings.width, settings.height).into(),
+                            title: settings.title.clone(),
+                            canvas: Some("#bevy".to_owned()),
+                            fit_canvas_to_parent: true,
+                            // Tells wasm not to override default event handling, like F5 and Ctrl+R
+                            prevent_default_event_handling: false,
+                            ..default()
+                        }),
+                        ..default()
+                    })
+                    .set(log),
+                screenshot::ScreenshotPlugin,
+            ));
+            if settings.fps_counter {
+                app.add_plugins(fps::FpsPlugin);
+            }
+        }
+
+        app.register_action("quit", settings.quit_keys.clone())
+            .insert_resource(settings)
+            .add_plugins(cursor::CursorPlugin)
+            .add_systems(Update, quit.run_if(no_input_focused));
+    }
+}
+
+fn quit(input: ActionInput, windows: Query<&Window>, mut exit: EventWriter<AppExit>) {
+    if input.just_pressed("quit") && windows.iter().any(|window| window.focused) {
+        exit.send(AppExit::Success);
+    }
+}
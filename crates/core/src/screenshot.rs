@@ -0,0 +1,43 @@
+use std::{
+    fs,
+    path::PathBuf,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use bevy::{
+    prelude::*,
+    render::view::screenshot::{Screenshot, save_to_disk},
+};
+use playground_ui::{KeyBindingsAppExt, action_just_pressed};
+
+use crate::AppSettings;
+
+pub struct ScreenshotPlugin;
+
+impl Plugin for ScreenshotPlugin {
+    fn build(&self, app: &mut App) {
+        app.register_action("screenshot", [KeyCode::F12])
+            .add_systems(
+                Update,
+                take_screenshot.run_if(action_just_pressed("screenshot")),
+            );
+    }
+}
+
+const SCREENSHOT_DIR: &str = "screenshots";
+
+fn take_screenshot(mut cmd: Commands, settings: Res<AppSettings>) {
+    if let Err(err) = fs::create_dir_all(SCREENSHOT_DIR) {
+        warn!("Failed to create {}: {}", SCREENSHOT_DIR, err);
+        return;
+    }
+    let time = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_millis();
+    let name = settings.title.to_lowercase().replace(' ', "_");
+    let path = PathBuf::from(SCREENSHOT_DIR).join(format!("{}-{}.png", name, time));
+    info!("Saving screenshot to {}", path.display());
+    cmd.spawn(Screenshot::primary_window())
+        .observe(save_to_disk(path));
+}
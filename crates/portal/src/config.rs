@@ -1,46 +1,49 @@
 use std::{
-    env,
     fmt::{self, Display},
     fs,
     str::FromStr,
 };
 
 use bevy::prelude::*;
+use playground_core::AppSettings;
+use playground_ui::FieldValue;
 use serde::{
     Deserialize,
     de::{self, MapAccess, Visitor},
 };
 
-use crate::{ParticleMesh, ParticleSpawnTimer, Portal, WINDOW_HEIGHT, WINDOW_WIDTH};
+use crate::{ParticleMesh, ParticleSpawnTimer, Portal};
 
 pub struct ConfigPlugin;
 
 impl Plugin for ConfigPlugin {
     fn build(&self, app: &mut App) {
-        let config_filepath = env::var("CARGO_MANIFEST_DIR").unwrap() + "/config.toml";
+        let config_filepath = concat!(env!("CARGO_MANIFEST_DIR"), "/config.toml");
         let config =
             toml::from_str::<Config>(&fs::read_to_string(config_filepath).unwrap()).unwrap();
 
         app.insert_resource(config)
+            .register_type::<Config>()
             .add_event::<ConfigChanged>()
             .add_observer(config_sync);
     }
 }
 
-#[derive(Resource, Deserialize)]
+#[derive(Resource, Reflect, Deserialize)]
+#[reflect(Resource)]
 pub struct Config {
     pub portal: PortalConfig,
     pub particle: ParticleConfig,
 }
 
-#[derive(Deserialize)]
+#[derive(Reflect, Deserialize)]
 pub struct PortalConfig {
     pub size: f32,
     pub pos: RelPos,
     pub edge_offset: f32,
 }
 
-#[derive(Deserialize)]
+#[derive(Reflect, Deserialize)]
 pub struct ParticleConfig {
     pub size: u32,
     pub spawn_interval: f32,
@@ -49,7 +52,7 @@ pub struct ParticleConfig {
     pub trail: TrailConfig,
 }
 
-#[derive(Deserialize)]
+#[derive(Reflect, Deserialize)]
 pub struct TrailConfig {
     pub spawn_interval: f32,
     pub timeout: f32,
@@ -68,7 +71,7 @@ pub enum ConfigChanged {
     ParticleTrailTimeout,
 }
 
-#[derive(Debug, Default)]
+#[derive(Debug, Default, Clone, Reflect)]
 pub enum RelPos {
     #[default]
     Center,
@@ -94,6 +97,16 @@ impl FromStr for RelPos {
     }
 }
 
+impl FieldValue for RelPos {
+    fn parse(text: &str) -> Option<Self> {
+        RelPos::from_str(text).ok()
+    }
+
+    fn format(&self) -> String {
+        self.to_string()
+    }
+}
+
 impl Display for RelPos {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         match self {
@@ -168,6 +181,7 @@ fn config_sync(
     mut particle_mesh: ResMut<ParticleMesh>,
     mut meshes: ResMut<Assets<Mesh>>,
     mut portal: Single<&mut Transform, With<Portal>>,
+    settings: Res<AppSettings>,
 ) {
     match *trigger {
         ConfigChanged::ParticleSpawnInterval => {
@@ -179,12 +193,13 @@ fn config_sync(
             particle_mesh.0 = meshes.add(Circle::new(config.particle.size as f32));
         }
         ConfigChanged::PortalPos => {
+            let (half_width, half_height) = (settings.width / 2., settings.height / 2.);
             let portal_pos = match config.portal.pos {
                 RelPos::Center => (0., 0.),
-                RelPos::TopRight => (WINDOW_WIDTH / 2., WINDOW_HEIGHT / 2.),
-                RelPos::TopLeft => (-WINDOW_WIDTH / 2., WINDOW_HEIGHT / 2.),
-                RelPos::BottomRight => (WINDOW_WIDTH / 2., -WINDOW_HEIGHT / 2.),
-                RelPos::BottomLeft => (-WINDOW_WIDTH / 2., -WINDOW_HEIGHT / 2.),
+                RelPos::TopRight => (half_width, half_height),
+                RelPos::TopLeft => (-half_width, half_height),
+                RelPos::BottomRight => (half_width, -half_height),
+                RelPos::BottomLeft => (-half_width, -half_height),
                 RelPos::Custom(x, y) => (x, y),
             };
             portal.translation = Vec3::new(portal_pos.0, portal_pos.1, 0.0);
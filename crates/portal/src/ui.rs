@@ -1,11 +1,9 @@
-use std::str::FromStr;
-
 use bevy::prelude::*;
-use bevy_simple_text_input::TextInputValue;
 use playground_ui::{
-    DebugLog, DebugPanelText, Header, InputField, InputFieldLabel, InputFieldType,
-    InputUISubmitEvent, InputUInitialValue, MaxWidth, Panel, PanelTitle, PlaygroundUIPlugin,
-    TextUI,
+    ConsolePlugin, DebugLog, DebugLogView, DebugWatch, DebugWatchView, Draggable, Header,
+    InputFieldLabel, InputFieldUnit, KeyBindingsAppExt, KeyBindingsView, Level, MaxHeight,
+    MaxWidth, Panel, PanelLayoutStore, PanelTitle, PlaygroundUIPlugin, Plot, Resizable, ScrollArea,
+    Toasts, Tooltip, ValueField, ValueFieldPlugin, ValueSubmitted,
 };
 
 use crate::config::{Config, ConfigChanged, RelPos};
@@ -14,10 +12,22 @@ pub struct UIPlugin;
 
 impl Plugin for UIPlugin {
     fn build(&self, app: &mut App) {
-        app.add_plugins(PlaygroundUIPlugin)
-            .init_resource::<DebugLog>()
-            .add_systems(Startup, build_ui)
-            .add_observer(control_panel_system);
+        let panel_layout_filepath = concat!(env!("CARGO_MANIFEST_DIR"), "/panel_layout.toml");
+        let key_bindings_filepath = concat!(env!("CARGO_MANIFEST_DIR"), "/key_bindings.toml");
+
+        app.add_plugins((
+            PlaygroundUIPlugin,
+            ConsolePlugin,
+            ValueFieldPlugin::<RelPos>::default(),
+        ))
+        .init_resource::<DebugLog>()
+        .init_resource::<DebugWatch>()
+        .insert_resource(Toasts::new().with_log_level(Level::Warn))
+        .insert_resource(PanelLayoutStore::load(panel_layout_filepath))
+        .load_key_bindings(key_bindings_filepath)
+        .add_systems(Startup, build_ui)
+        .add_systems(Update, watch_frame_time)
+        .add_observer(control_panel_system);
     }
 }
 
@@ -39,139 +49,169 @@ fn build_ui(mut cmd: Commands, config: Res<Config>) {
             .spawn((
                 Panel,
                 PanelTitle::new("Control Panel"),
+                Tooltip::new("Changes apply on Enter, Ctrl+Z undoes them"),
                 MaxWidth(Val::Percent(10.)),
+                Draggable,
+                Resizable,
             ))
             .with_children(|parent| {
-                parent.spawn(Header::new("Portal"));
-                parent.spawn((
-                    InputField,
-                    InputFieldLabel::new("size"),
-                    InputUInitialValue(config.portal.size.to_string()),
-                    InputFieldType::F32,
-                    Name::new("portal:size"),
-                ));
-                parent.spawn((
-                    InputField,
-                    InputFieldLabel::new("position"),
-                    InputUInitialValue(config.portal.pos.to_string()),
-                    InputFieldType::String,
-                    MaxWidth(Val::Px(85.)),
-                    Name::new("portal:pos"),
-                ));
-                parent.spawn((
-                    InputField,
-                    InputFieldLabel::new("edge offset"),
-                    InputUInitialValue(config.portal.edge_offset.to_string()),
-                    InputFieldType::F32,
-                    Name::new("portal:edge_offset"),
-                ));
-                parent.spawn(Header::new("Particle"));
-                parent.spawn((
-                    InputField,
-                    InputFieldLabel::new("size"),
-                    InputUInitialValue(config.particle.size.to_string()),
-                    InputFieldType::F32,
-                    Name::new("particle:size"),
-                ));
-                parent.spawn((
-                    InputField,
-                    InputFieldLabel::new("spawn interval"),
-                    InputUInitialValue(config.particle.spawn_interval.to_string()),
-                    InputFieldType::F32,
-                    Name::new("particle:spawn_interval"),
-                ));
-                parent.spawn((
-                    InputField,
-                    InputFieldLabel::new("move speed"),
-                    InputUInitialValue(config.particle.move_speed.to_string()),
-                    InputFieldType::F32,
-                    Name::new("particle:move_speed"),
-                ));
-                parent.spawn((
-                    InputField,
-                    InputFieldLabel::new("spiral angle"),
-                    InputUInitialValue(config.particle.spiral_offset_angle.to_string()),
-                    InputFieldType::F32,
-                    Name::new("particle:spiral_offset_angle"),
-                ));
-                parent.spawn(Header::new("Particle Trail"));
-                parent.spawn((
-                    InputField,
-                    InputFieldLabel::new("spawn interval"),
-                    InputUInitialValue(config.particle.trail.spawn_interval.to_string()),
-                    InputFieldType::F32,
-                    Name::new("particle:trail:spawn_interval"),
-                ));
-                parent.spawn((
-                    InputField,
-                    InputFieldLabel::new("timeout"),
-                    InputUInitialValue(config.particle.trail.timeout.to_string()),
-                    InputFieldType::F32,
-                    Name::new("particle:trail:timeout"),
-                ));
+                parent
+                    .spawn((ScrollArea, MaxHeight(Val::Vh(60.))))
+                    .with_children(|parent| {
+                        parent.spawn(Header::new("Portal"));
+                        parent.spawn((
+                            ValueField::new(config.portal.size).with_min(0.),
+                            InputFieldLabel::new("size"),
+                            InputFieldUnit("px".to_string()),
+                            Tooltip::new("Radius of the ring particles spawn on"),
+                            Name::new("portal:size"),
+                        ));
+                        parent.spawn((
+                            ValueField::new(config.portal.pos.clone()),
+                            InputFieldLabel::new("position"),
+                            MaxWidth(Val::Px(85.)),
+                            Tooltip::new("center, topleft, topright, bottomleft or bottomright"),
+                            Name::new("portal:pos"),
+                        ))
+                        .observe(set_portal_pos);
+                        parent.spawn((
+                            ValueField::new(config.portal.edge_offset).with_min(0.),
+                            InputFieldLabel::new("edge offset"),
+                            InputFieldUnit("px".to_string()),
+                            Tooltip::new("How far particles can spawn inside or outside the ring"),
+                            Name::new("portal:edge_offset"),
+                        ));
+                        parent.spawn(Header::new("Particle"));
+                        parent.spawn((
+                            ValueField::new(config.particle.size).with_min(1),
+                            InputFieldLabel::new("size"),
+                            InputFieldUnit("px".to_string()),
+                            Tooltip::new("Particle radius, whole numbers only"),
+                            Name::new("particle:size"),
+                        ))
+                        .observe(set_particle_size);
+                        parent.spawn((
+                            ValueField::new(config.particle.spawn_interval).with_min(0.01),
+                            InputFieldLabel::new("spawn interval"),
+                            InputFieldUnit("s".to_string()),
+                            Tooltip::new("Time between two particles spawning"),
+                            Name::new("particle:spawn_interval"),
+                        ));
+                        parent.spawn((
+                            ValueField::new(config.particle.move_speed),
+                            InputFieldLabel::new("move speed"),
+                            InputFieldUnit("px/s".to_string()),
+                            Tooltip::new("Speed particles move towards the center"),
+                            Name::new("particle:move_speed"),
+                        ));
+                        parent.spawn((
+                            ValueField::new(config.particle.spiral_offset_angle),
+                            InputFieldLabel::new("spiral angle"),
+                            InputFieldUnit("°".to_string()),
+                            Tooltip::new("Angle between the particle direction and the center, 0 moves straight in"),
+                            Name::new("particle:spiral_offset_angle"),
+                        ));
+                        parent.spawn((
+                            Header::new("Particle Trail"),
+                            Tooltip::new("Dots left behind by each particle"),
+                        ));
+                        parent.spawn((
+                            ValueField::new(config.particle.trail.spawn_interval).with_min(0.01),
+                            InputFieldLabel::new("spawn interval"),
+                            InputFieldUnit("s".to_string()),
+                            Tooltip::new("Time between two trail dots of a particle"),
+                            Name::new("particle:trail:spawn_interval"),
+                        ));
+                        parent.spawn((
+                            ValueField::new(config.particle.trail.timeout).with_min(0.),
+                            InputFieldLabel::new("timeout"),
+                            InputFieldUnit("s".to_string()),
+                            Tooltip::new("How long a trail dot takes to shrink away"),
+                            Name::new("particle:trail:timeout"),
+                        ));
+                    });
+                parent
+                    .spawn((Panel, PanelTitle::new("Watch")))
+                    .with_children(|parent| {
+                        parent.spawn(DebugWatchView);
+                        parent.spawn(Plot::new("frame_time"));
+                        parent.spawn(Plot::new("particles"));
+                    });
                 parent
                     .spawn((Panel, PanelTitle::new("Debug")))
                     .with_children(|parent| {
-                        parent.spawn((DebugPanelText, TextUI::new("")));
+                        parent.spawn(DebugLogView);
+                    });
+                parent
+                    .spawn((Panel, PanelTitle::new("Controls")))
+                    .with_children(|parent| {
+                        parent.spawn(KeyBindingsView);
                     });
             });
     });
 }
 
+fn watch_frame_time(time: Res<Time>, mut debug_watch: ResMut<DebugWatch>) {
+    debug_watch.watch("frame_time", time.delta_secs() * 1000.);
+}
+
 fn control_panel_system(
-    trigger: Trigger<InputUISubmitEvent>,
+    trigger: Trigger<ValueSubmitted<f32>>,
     mut cmd: Commands,
-    mut input: Query<(&mut TextInputValue, &Name)>,
+    name: Query<&Name>,
     mut config: ResMut<Config>,
 ) {
-    let (mut value, name) = input.get_mut(trigger.entity()).unwrap();
-    if name.eq(&Name::new("portal:size")) {
-        if let Ok(size) = value.0.parse::<f32>() {
-            config.portal.size = size;
+    let value = trigger.value;
+    let Ok(name) = name.get(trigger.entity()) else {
+        return;
+    };
+    match name.as_str() {
+        "portal:size" => {
+            config.portal.size = value;
             cmd.trigger(ConfigChanged::PortalSize);
         }
-    } else if name.eq(&Name::new("portal:pos")) {
-        if let Ok(pos) = RelPos::from_str(&value.0) {
-            config.portal.pos = pos;
-            cmd.trigger(ConfigChanged::PortalPos);
-        } else {
-            warn!("Invalid portal position");
-            value.0 = config.portal.pos.to_string();
-        }
-    } else if name.eq(&Name::new("portal:edge_offset")) {
-        if let Ok(edge_offset) = value.0.parse::<f32>() {
-            config.portal.edge_offset = edge_offset;
+        "portal:edge_offset" => {
+            config.portal.edge_offset = value;
             cmd.trigger(ConfigChanged::PortalEdgeOffset);
         }
-    } else if name.eq(&Name::new("particle:size")) {
-        if let Ok(size) = value.0.parse::<u32>() {
-            config.particle.size = size;
-            cmd.trigger(ConfigChanged::ParticleSize);
-        }
-    } else if name.eq(&Name::new("particle:spawn_interval")) {
-        if let Ok(spawn_interval) = value.0.parse::<f32>() {
-            config.particle.spawn_interval = spawn_interval;
+        "particle:spawn_interval" => {
+            config.particle.spawn_interval = value;
             cmd.trigger(ConfigChanged::ParticleSpawnInterval);
         }
-    } else if name.eq(&Name::new("particle:move_speed")) {
-        if let Ok(move_speed) = value.0.parse::<f32>() {
-            config.particle.move_speed = move_speed;
+        "particle:move_speed" => {
+            config.particle.move_speed = value;
             cmd.trigger(ConfigChanged::ParticleMoveSpeed);
         }
-    } else if name.eq(&Name::new("particle:spiral_offset_angle")) {
-        if let Ok(spiral_offset_angle) = value.0.parse::<f32>() {
-            config.particle.spiral_offset_angle = spiral_offset_angle;
+        "particle:spiral_offset_angle" => {
+            config.particle.spiral_offset_angle = value;
             cmd.trigger(ConfigChanged::ParticleSpiralOffsetAngle);
         }
-    } else if name.eq(&Name::new("particle:trail:spawn_interval")) {
-        if let Ok(spawn_interval) = value.0.parse::<f32>() {
-            config.particle.trail.spawn_interval = spawn_interval;
+        "particle:trail:spawn_interval" => {
+            config.particle.trail.spawn_interval = value;
             cmd.trigger(ConfigChanged::ParticleTrailSpawnInterval);
         }
-    } else if name.eq(&Name::new("particle:trail:timeout")) {
-        if let Ok(timeout) = value.0.parse::<f32>() {
-            config.particle.trail.timeout = timeout;
+        "particle:trail:timeout" => {
+            config.particle.trail.timeout = value;
             cmd.trigger(ConfigChanged::ParticleTrailTimeout);
         }
+        _ => {}
     }
 }
+
+fn set_portal_pos(
+    trigger: Trigger<ValueSubmitted<RelPos>>,
+    mut cmd: Commands,
+    mut config: ResMut<Config>,
+) {
+    config.portal.pos = trigger.value.clone();
+    cmd.trigger(ConfigChanged::PortalPos);
+}
+
+fn set_particle_size(
+    trigger: Trigger<ValueSubmitted<u32>>,
+    mut cmd: Commands,
+    mut config: ResMut<Config>,
+) {
+    config.particle.size = trigger.value;
+    cmd.trigger(ConfigChanged::ParticleSize);
+}
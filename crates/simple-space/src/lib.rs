@@ -0,0 +1,41 @@
+use avian2d::prelude::*;
+use bevy::prelude::*;
+use bevy_prototype_lyon::plugin::ShapePlugin;
+use bevy_rand::{plugin::EntropyPlugin, prelude::WyRand};
+use bevy_vector_shapes::Shape2dPlugin;
+use modules::*;
+use ui::UIPlugin;
+
+mod constant;
+pub mod modules;
+mod ui;
+
+/// Top down space shooter, expects
+/// [`PlaygroundAppPlugin`](playground_core::PlaygroundAppPlugin) to be added.
+pub struct SimpleSpacePlugin;
+
+impl Plugin for SimpleSpacePlugin {
+    fn build(&self, app: &mut App) {
+        app.add_plugins(PhysicsPlugins::default().with_length_unit(20.))
+            // .add_plugins(PhysicsDebugPlugin::default())
+            .add_plugins(ShapePlugin)
+            .add_plugins(Shape2dPlugin::default())
+            .add_plugins(UIPlugin)
+            .add_plugins(GameStatePlugin)
+            .add_plugins(ShipPlugin)
+            .add_plugins(AsteroidPlugin)
+            .add_plugins(EnemyPlugin)
+            .add_plugins(HealthPlugin)
+            .add_plugins(DamagePlugin)
+            .add_plugins(DeathPlugin)
+            .add_plugins(LivesPlugin)
+            .add_plugins(PickupPlugin)
+            .add_plugins(BulletPlugin)
+            .add_plugins(WeaponPlugin)
+            .add_plugins(AmmoPlugin)
+            .add_plugins(AbilityPlugin)
+            .add_plugins(VfxPlugin)
+            .add_plugins(CameraRigPlugin)
+            .add_plugins(EntropyPlugin::<WyRand>::default());
+    }
+}
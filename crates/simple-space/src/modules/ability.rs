@@ -0,0 +1,292 @@
+use std::f32::consts::TAU;
+
+use avian2d::prelude::*;
+use bevy::prelude::*;
+use bevy_prototype_lyon::prelude::*;
+use bevy_vector_shapes::prelude::*;
+
+use super::{damage::DamageEvent, game_state::GameState, health::Dead};
+
+pub struct AbilityPlugin;
+
+impl Plugin for AbilityPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            Update,
+            (
+                perform_dashes,
+                end_dashes,
+                drain_boosts,
+                tick_invulnerability,
+                blink_invulnerable,
+            )
+                .run_if(in_state(GameState::Playing)),
+        )
+        .add_systems(Update, draw_cooldowns)
+        .add_observer(cut_force_on_dash)
+        .add_observer(ignore_damage_while_invulnerable)
+        .add_observer(stop_blinking);
+    }
+}
+
+/// Burst of velocity with brief invulnerability, used through
+/// [`Dash::activate`].
+#[derive(Component, Clone, Debug)]
+pub struct Dash {
+    pub speed: f32,
+    /// Seconds of the burst, the entity is [`Dashing`] meanwhile.
+    pub duration: f32,
+    /// Seconds of [`Invulnerable`] from the start of the dash.
+    pub invulnerability: f32,
+    pub cooldown: f32,
+    remaining: f32,
+    requested: Option<Vec2>,
+}
+
+impl Dash {
+    pub fn new(speed: f32, cooldown: f32) -> Self {
+        Self {
+            speed,
+            duration: 0.15,
+            invulnerability: 0.3,
+            cooldown,
+            remaining: 0.,
+            requested: None,
+        }
+    }
+
+    pub fn with_invulnerability(mut self, invulnerability: f32) -> Self {
+        self.invulnerability = invulnerability;
+        self
+    }
+
+    /// Dashes toward `direction` next update, returns false while cooling
+    /// down.
+    pub fn activate(&mut self, direction: Vec2) -> bool {
+        if !self.is_ready() || direction == Vec2::ZERO {
+            return false;
+        }
+        self.requested = Some(direction.normalize());
+        true
+    }
+
+    pub fn is_ready(&self) -> bool {
+        self.remaining <= 0. && self.requested.is_none()
+    }
+
+    /// Fraction of the cooldown that has passed, 1 when ready.
+    pub fn charge(&self) -> f32 {
+        if self.cooldown <= 0. {
+            return 1.;
+        }
+        1. - (self.remaining / self.cooldown).clamp(0., 1.)
+    }
+}
+
+/// The entity is in the middle of a [`Dash`], movement controls should leave
+/// its velocity alone.
+#[derive(Component)]
+pub struct Dashing(Timer);
+
+/// Raises the speed cap of the entity while `active`, draining a meter that
+/// refills when not boosting.
+#[derive(Component, Clone, Debug)]
+pub struct Boost {
+    pub multiplier: f32,
+    /// Meter spent per second, a full meter is 1.
+    pub drain: f32,
+    /// Meter regained per second when not boosting.
+    pub regen: f32,
+    pub active: bool,
+    meter: f32,
+}
+
+impl Boost {
+    pub fn new(multiplier: f32) -> Self {
+        Self {
+            multiplier,
+            drain: 0.5,
+            regen: 0.2,
+            active: false,
+            meter: 1.,
+        }
+    }
+
+    pub fn is_boosting(&self) -> bool {
+        self.active && self.meter > 0.
+    }
+
+    /// What the speed cap is multiplied by this frame.
+    pub fn speed_multiplier(&self) -> f32 {
+        if self.is_boosting() {
+            self.multiplier
+        } else {
+            1.
+        }
+    }
+
+    pub fn meter(&self) -> f32 {
+        self.meter
+    }
+}
+
+/// Cancels all [`DamageEvent`]s on this entity until the timer finishes.
+/// The entity's outline blinks meanwhile.
+#[derive(Component)]
+pub struct Invulnerable(pub Timer);
+
+impl Invulnerable {
+    pub fn new(seconds: f32) -> Self {
+        Self(Timer::from_seconds(seconds, TimerMode::Once))
+    }
+}
+
+fn perform_dashes(
+    mut cmd: Commands,
+    mut dashes: Query<
+        (
+            Entity,
+            &mut Dash,
+            &mut LinearVelocity,
+            Option<&mut Invulnerable>,
+        ),
+        Without<Dead>,
+    >,
+    time: Res<Time>,
+) {
+    for (id, mut dash, mut linvel, invulnerable) in dashes.iter_mut() {
+        dash.remaining = (dash.remaining - time.delta_secs()).max(0.);
+        let Some(direction) = dash.requested.take() else {
+            continue;
+        };
+        dash.remaining = dash.cooldown;
+        linvel.0 = direction * dash.speed;
+        cmd.entity(id)
+            .insert(Dashing(Timer::from_seconds(dash.duration, TimerMode::Once)));
+        match invulnerable {
+            // Don't cut a longer invulnerability short
+            Some(mut invulnerable) => {
+                let remaining = invulnerable.0.remaining_secs().max(dash.invulnerability);
+                *invulnerable = Invulnerable::new(remaining);
+            }
+            None => {
+                cmd.entity(id)
+                    .insert(Invulnerable::new(dash.invulnerability));
+            }
+        }
+    }
+}
+
+fn end_dashes(mut cmd: Commands, mut dashing: Query<(Entity, &mut Dashing)>, time: Res<Time>) {
+    for (id, mut dashing) in dashing.iter_mut() {
+        if dashing.0.tick(time.delta()).finished() {
+            cmd.entity(id).remove::<Dashing>();
+        }
+    }
+}
+
+/// Thrusters would push on top of the dash, they're idle until it ends.
+fn cut_force_on_dash(trigger: Trigger<OnAdd, Dashing>, mut force: Query<&mut ExternalForce>) {
+    if let Ok(mut force) = force.get_mut(trigger.entity()) {
+        force.clear();
+    }
+}
+
+fn drain_boosts(mut boosts: Query<&mut Boost>, time: Res<Time>) {
+    for mut boost in boosts.iter_mut() {
+        boost.meter = if boost.active {
+            (boost.meter - boost.drain * time.delta_secs()).max(0.)
+        } else {
+            (boost.meter + boost.regen * time.delta_secs()).min(1.)
+        };
+    }
+}
+
+fn tick_invulnerability(
+    mut cmd: Commands,
+    mut invulnerable: Query<(Entity, &mut Invulnerable)>,
+    time: Res<Time>,
+) {
+    for (id, mut invulnerable) in invulnerable.iter_mut() {
+        if invulnerable.0.tick(time.delta()).finished() {
+            cmd.entity(id).remove::<Invulnerable>();
+        }
+    }
+}
+
+fn ignore_damage_while_invulnerable(
+    mut trigger: Trigger<DamageEvent>,
+    invulnerable: Query<(), With<Invulnerable>>,
+) {
+    if invulnerable.contains(trigger.entity()) {
+        trigger.event_mut().cancel();
+    }
+}
+
+/// Seconds per on/off cycle of an invulnerable entity's outline.
+const BLINK_PERIOD: f32 = 0.1;
+
+fn blink_invulnerable(
+    invulnerable: Query<&Children, With<Invulnerable>>,
+    mut strokes: Query<&mut Stroke>,
+    time: Res<Time>,
+) {
+    let alpha = if (time.elapsed_secs() / BLINK_PERIOD) as u32 % 2 == 0 {
+        0.2
+    } else {
+        1.
+    };
+    for children in invulnerable.iter() {
+        let mut strokes = strokes.iter_many_mut(children);
+        while let Some(mut stroke) = strokes.fetch_next() {
+            stroke.color.set_alpha(alpha);
+        }
+    }
+}
+
+fn stop_blinking(
+    trigger: Trigger<OnRemove, Invulnerable>,
+    children: Query<&Children>,
+    mut strokes: Query<&mut Stroke>,
+) {
+    let Ok(children) = children.get(trigger.entity()) else {
+        return;
+    };
+    let mut strokes = strokes.iter_many_mut(children);
+    while let Some(mut stroke) = strokes.fetch_next() {
+        stroke.color.set_alpha(1.);
+    }
+}
+
+const COOLDOWN_RADIUS: f32 = 130.;
+
+/// Arcs around the entity for a [`Dash`] that's cooling down and a [`Boost`]
+/// meter that isn't full.
+fn draw_cooldowns(
+    mut painter: ShapePainter,
+    abilities: Query<
+        (&GlobalTransform, &Visibility, Option<&Dash>, Option<&Boost>),
+        (Or<(With<Dash>, With<Boost>)>, Without<Dead>),
+    >,
+) {
+    painter.hollow = true;
+    painter.thickness = 3.;
+    for (transform, visibility, dash, boost) in abilities.iter() {
+        if *visibility == Visibility::Hidden {
+            continue;
+        }
+        painter.transform = Transform::from_translation(transform.translation());
+        if let Some(dash) = dash.filter(|dash| dash.charge() < 1.) {
+            painter.color = Color::WHITE.with_alpha(0.6);
+            painter.arc(COOLDOWN_RADIUS, 0., dash.charge() * TAU);
+        }
+        if let Some(boost) = boost.filter(|boost| boost.meter() < 1.) {
+            painter.color = if boost.is_boosting() {
+                Color::srgb(1., 0.6, 0.2)
+            } else {
+                Color::srgb(0.5, 0.5, 0.5)
+            };
+            painter.arc(COOLDOWN_RADIUS + 8., 0., boost.meter() * TAU);
+        }
+    }
+}
@@ -0,0 +1,351 @@
+use avian2d::prelude::*;
+use bevy::{prelude::*, sprite::Anchor};
+use bevy_prototype_lyon::prelude::*;
+use serde::Deserialize;
+
+use super::{
+    game_state::GameState,
+    health::{Dead, HPBarConfig},
+    weapon::Weapon,
+};
+
+pub struct AmmoPlugin;
+
+impl Plugin for AmmoPlugin {
+    fn build(&self, app: &mut App) {
+        app.register_type::<EnergyPool>()
+            .add_systems(Update, regen_energy.run_if(in_state(GameState::Playing)))
+            .add_systems(Update, (sync_ammo_bar_position, sync_ammo_bar))
+            .add_observer(spawn_ammo_bar)
+            .add_observer(despawn_ammo_bar);
+    }
+}
+
+/// What a weapon spends when it fires.
+#[derive(Clone, Debug, Default, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum Ammo {
+    #[default]
+    Unlimited,
+    /// `size` rounds, then `reload` seconds to refill. Beams spend one round
+    /// per second.
+    Magazine { size: u32, reload: f32 },
+    /// Each shot adds `per_shot` heat, or that much per second for beams.
+    /// Heat drops by `cooling` per second, at 1 the weapon overheats and
+    /// can't fire until it has cooled down completely.
+    Heat { per_shot: f32, cooling: f32 },
+    /// Each shot costs `per_shot` from the owner's [`EnergyPool`], or that much
+    /// per second for beams.
+    Energy { per_shot: f32 },
+}
+
+/// Energy shared by all weapons of an entity.
+#[derive(Component, Reflect, Clone, Copy, Debug)]
+#[reflect(Component)]
+pub struct EnergyPool {
+    pub current: f32,
+    pub max: f32,
+    /// Energy regained per second.
+    pub regen: f32,
+}
+
+impl EnergyPool {
+    pub fn new(max: f32) -> Self {
+        Self {
+            current: max,
+            max,
+            regen: 0.,
+        }
+    }
+
+    pub fn with_regen(mut self, regen: f32) -> Self {
+        self.regen = regen;
+        self
+    }
+}
+
+/// Rounds left, reload and heat of one weapon.
+#[derive(Clone, Debug, Default)]
+pub struct AmmoState {
+    rounds: f32,
+    /// Seconds until the magazine is full again, zero when not reloading.
+    reload: f32,
+    heat: f32,
+    overheated: bool,
+}
+
+impl AmmoState {
+    pub fn new(ammo: &Ammo) -> Self {
+        match ammo {
+            Ammo::Magazine { size, .. } => Self {
+                rounds: *size as f32,
+                ..default()
+            },
+            _ => default(),
+        }
+    }
+
+    pub fn tick(&mut self, ammo: &Ammo, delta: f32) {
+        match ammo {
+            Ammo::Magazine { size, .. } if self.reload > 0. => {
+                self.reload = (self.reload - delta).max(0.);
+                if self.reload == 0. {
+                    self.rounds = *size as f32;
+                }
+            }
+            Ammo::Heat { cooling, .. } => {
+                self.heat = (self.heat - cooling * delta).max(0.);
+                if self.heat == 0. {
+                    self.overheated = false;
+                }
+            }
+            _ => {}
+        }
+    }
+
+    /// Whether `amount` shots (or seconds for beams) can be spent.
+    pub fn can_fire(&self, ammo: &Ammo, energy: Option<&EnergyPool>, amount: f32) -> bool {
+        match ammo {
+            Ammo::Unlimited => true,
+            Ammo::Magazine { .. } => self.reload == 0. && self.rounds > 0.,
+            Ammo::Heat { .. } => !self.overheated,
+            Ammo::Energy { per_shot } => {
+                energy.is_some_and(|energy| energy.current >= per_shot * amount)
+            }
+        }
+    }
+
+    pub fn spend(&mut self, ammo: &Ammo, energy: Option<Mut<EnergyPool>>, amount: f32) {
+        match ammo {
+            Ammo::Unlimited => {}
+            Ammo::Magazine { reload, .. } => {
+                self.rounds = (self.rounds - amount).max(0.);
+                if self.rounds == 0. {
+                    self.reload = *reload;
+                }
+            }
+            Ammo::Heat { per_shot, .. } => {
+                self.heat = (self.heat + per_shot * amount).min(1.);
+                if self.heat >= 1. {
+                    self.overheated = true;
+                }
+            }
+            Ammo::Energy { per_shot } => {
+                if let Some(mut energy) = energy {
+                    energy.current = (energy.current - per_shot * amount).max(0.);
+                }
+            }
+        }
+    }
+
+    /// Starts reloading a magazine that isn't full.
+    pub fn reload(&mut self, ammo: &Ammo) {
+        let Ammo::Magazine { size, reload } = ammo else {
+            return;
+        };
+        if self.reload == 0. && self.rounds < *size as f32 {
+            self.reload = *reload;
+        }
+    }
+
+    pub fn rounds(&self) -> u32 {
+        self.rounds.ceil() as u32
+    }
+
+    pub fn heat(&self) -> f32 {
+        self.heat
+    }
+
+    pub fn is_reloading(&self) -> bool {
+        self.reload > 0.
+    }
+
+    pub fn is_overheated(&self) -> bool {
+        self.overheated
+    }
+}
+
+fn regen_energy(mut pools: Query<&mut EnergyPool, Without<Dead>>, time: Res<Time>) {
+    for mut pool in pools.iter_mut() {
+        if pool.current < pool.max {
+            pool.current = (pool.current + pool.regen * time.delta_secs()).min(pool.max);
+        }
+    }
+}
+
+#[derive(Component)]
+#[require(Transform, Visibility)]
+struct AmmoBar {
+    y_offset: f32,
+}
+
+#[derive(Component, Clone, Copy)]
+struct AmmoBarRef(Entity);
+
+#[derive(Component)]
+struct AmmoBarFill;
+
+#[derive(Component)]
+struct AmmoBarLabel;
+
+const AMMO_BAR_WIDTH: f32 = 100.;
+const AMMO_BAR_HEIGHT: f32 = 4.;
+
+/// Seconds per on/off cycle of the bar when the trigger is held but the weapon
+/// can't fire.
+const BLINK_PERIOD: f32 = 0.2;
+
+fn spawn_ammo_bar(trigger: Trigger<OnAdd, Weapon>, mut cmd: Commands, config: Query<&HPBarConfig>) {
+    // Right below the HP bar
+    let y_offset = config
+        .get(trigger.entity())
+        .map_or(0., |config| config.y_offset)
+        - 15.;
+    let rectangle = shapes::Rectangle {
+        extents: Vec2::new(AMMO_BAR_WIDTH, AMMO_BAR_HEIGHT),
+        origin: RectangleOrigin::TopLeft,
+        ..default()
+    };
+    let bar = cmd
+        .spawn((AmmoBar { y_offset }, Visibility::Hidden))
+        .with_children(|parent| {
+            parent.spawn((
+                ShapeBundle {
+                    path: GeometryBuilder::build_as(&rectangle),
+                    transform: Transform::from_xyz(-AMMO_BAR_WIDTH / 2., 0., 0.),
+                    ..default()
+                },
+                Fill::color(Color::BLACK),
+                Stroke::new(Color::WHITE, 1.),
+            ));
+            parent.spawn((
+                AmmoBarFill,
+                ShapeBundle {
+                    path: GeometryBuilder::build_as(&rectangle),
+                    transform: Transform::from_xyz(-AMMO_BAR_WIDTH / 2., 0., 0.1),
+                    ..default()
+                },
+                Fill::color(Color::WHITE),
+            ));
+            parent.spawn((
+                AmmoBarLabel,
+                Text2d::default(),
+                TextFont::from_font_size(24.),
+                Anchor::CenterLeft,
+                Transform::from_xyz(AMMO_BAR_WIDTH / 2. + 10., -AMMO_BAR_HEIGHT / 2., 0.),
+            ));
+        })
+        .id();
+    cmd.entity(trigger.entity()).insert(AmmoBarRef(bar));
+}
+
+fn despawn_ammo_bar(
+    trigger: Trigger<OnRemove, AmmoBarRef>,
+    mut cmd: Commands,
+    bar: Query<&AmmoBarRef>,
+) {
+    let Ok(AmmoBarRef(bar)) = bar.get(trigger.entity()) else {
+        return;
+    };
+    if let Some(mut bar) = cmd.get_entity(*bar) {
+        bar.try_despawn_recursive();
+    }
+}
+
+fn sync_ammo_bar_position(
+    owner: Query<(&Position, &AmmoBarRef), Changed<Position>>,
+    mut bar: Query<(&mut Transform, &AmmoBar)>,
+) {
+    for (position, AmmoBarRef(bar_id)) in owner.iter() {
+        if let Ok((mut bar_transform, bar)) = bar.get_mut(*bar_id) {
+            bar_transform.translation.x = position.x;
+            bar_transform.translation.y = position.y + bar.y_offset;
+        }
+    }
+}
+
+fn sync_ammo_bar(
+    owner: Query<(
+        &Weapon,
+        Option<&EnergyPool>,
+        Option<&Visibility>,
+        &AmmoBarRef,
+    )>,
+    mut bar: Query<(&mut Visibility, &Children), (With<AmmoBar>, Without<AmmoBarRef>)>,
+    mut fill: Query<(&mut Path, &mut Fill), With<AmmoBarFill>>,
+    mut label: Query<(&mut Text2d, &mut TextColor), With<AmmoBarLabel>>,
+    time: Res<Time>,
+) {
+    for (weapon, energy, owner_visibility, AmmoBarRef(bar_id)) in owner.iter() {
+        let Ok((mut visibility, children)) = bar.get_mut(*bar_id) else {
+            continue;
+        };
+        let ammo = &weapon.def.ammo;
+        let state = weapon.ammo();
+        if owner_visibility == Some(&Visibility::Hidden) {
+            visibility.set_if_neq(Visibility::Hidden);
+            continue;
+        }
+        let (fraction, text, color) = match ammo {
+            Ammo::Unlimited => {
+                visibility.set_if_neq(Visibility::Hidden);
+                continue;
+            }
+            Ammo::Magazine { reload, .. } if state.is_reloading() => (
+                1. - state.reload / reload.max(f32::EPSILON),
+                "RELOADING".to_string(),
+                Color::srgb(0.5, 0.5, 0.5),
+            ),
+            Ammo::Magazine { size, .. } => (
+                state.rounds / (*size).max(1) as f32,
+                format!("{}/{}", state.rounds(), size),
+                Color::WHITE,
+            ),
+            Ammo::Heat { .. } if state.is_overheated() => (
+                state.heat,
+                "OVERHEAT".to_string(),
+                Color::srgb(1., 0.2, 0.2),
+            ),
+            Ammo::Heat { .. } => (
+                state.heat,
+                String::new(),
+                Color::srgb(1., 1. - state.heat * 0.6, 1. - state.heat),
+            ),
+            Ammo::Energy { per_shot } => {
+                let energy = energy.copied().unwrap_or(EnergyPool::new(0.));
+                let text = if energy.current < *per_shot {
+                    "LOW ENERGY".to_string()
+                } else {
+                    String::new()
+                };
+                let fraction = energy.current / energy.max.max(f32::EPSILON);
+                (fraction, text, Color::srgb(0.3, 0.7, 1.))
+            }
+        };
+        visibility.set_if_neq(Visibility::Inherited);
+        // Blink while the trigger is held in vain
+        let blink = weapon.is_blocked() && (time.elapsed_secs() / BLINK_PERIOD) as u32 % 2 == 0;
+        let color = if blink {
+            Color::srgb(1., 0., 0.)
+        } else {
+            color
+        };
+
+        let mut fills = fill.iter_many_mut(children);
+        while let Some((mut path, mut fill)) = fills.fetch_next() {
+            *path = ShapePath::build_as(&shapes::Rectangle {
+                extents: Vec2::new(fraction.clamp(0., 1.) * AMMO_BAR_WIDTH, AMMO_BAR_HEIGHT),
+                origin: RectangleOrigin::TopLeft,
+                ..default()
+            });
+            fill.color = color;
+        }
+        let mut labels = label.iter_many_mut(children);
+        while let Some((mut label, mut label_color)) = labels.fetch_next() {
+            if label.0 != text {
+                label.0 = text.clone();
+            }
+            label_color.0 = color;
+        }
+    }
+}
@@ -6,6 +6,7 @@ use crate::constant;
 
 use super::{
     block::{Block, BlockProp},
+    game_state::InGame,
     template::TemplateExt,
 };
 
@@ -13,23 +14,28 @@ pub struct AsteroidPlugin;
 
 impl Plugin for AsteroidPlugin {
     fn build(&self, app: &mut App) {
-        app.add_systems(Startup, setup);
+        app.add_systems(OnEnter(InGame), setup);
     }
 }
 
 fn setup(mut cmd: Commands, rng: GlobalEntropy<WyRand>) {
     let asteroid_blocks = generate_asteroid(60, rng);
-    cmd.spawn((Asteroid, Transform::from_xyz(300., 300., 0.)))
-        .with_children(|parent| {
-            for (x, y) in asteroid_blocks {
-                parent.template::<Block>(BlockProp {
-                    position: Vec2::new(
-                        x as f32 * constant::BLOCK_SIZE,
-                        y as f32 * constant::BLOCK_SIZE,
-                    ),
-                });
-            }
-        });
+    cmd.spawn((
+        Asteroid,
+        Name::new("Asteroid"),
+        StateScoped(InGame),
+        Transform::from_xyz(300., 300., 0.),
+    ))
+    .with_children(|parent| {
+        for (x, y) in asteroid_blocks {
+            parent.template::<Block>(BlockProp {
+                position: Vec2::new(
+                    x as f32 * constant::BLOCK_SIZE,
+                    y as f32 * constant::BLOCK_SIZE,
+                ),
+            });
+        }
+    });
 }
 
 #[derive(Component)]
@@ -19,6 +19,7 @@ impl Template for Block {
     fn construct(mut cmd: EntityCommands<'_>, prop: Self::Prop) -> EntityCommands<'_> {
         cmd.insert((
             Block,
+            Name::new("Block"),
             ShapeBundle {
                 path: GeometryBuilder::build_as(&shapes::Rectangle {
                     extents: Vec2::new(constant::BLOCK_SIZE, constant::BLOCK_SIZE),
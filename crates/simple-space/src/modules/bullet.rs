@@ -1,11 +1,18 @@
-use avian2d::{math::PI, prelude::*};
+use avian2d::{
+    math::{PI, TAU},
+    prelude::*,
+};
 use bevy::prelude::*;
 use bevy_prototype_lyon::prelude::*;
 use bevy_rand::{global::GlobalEntropy, prelude::WyRand, traits::ForkableRng};
-use playground_ui::DebugLog;
+use playground_ui::{DebugLog, Level};
+use serde::Deserialize;
 
 use super::{
-    health::Health,
+    block::Block,
+    damage::{DamageEvent, DamageKind},
+    game_state::{GameState, InGame},
+    health::{Dead, Health},
     template::{Template, TemplateExt},
     vfx::{Vfx, VfxProp, VfxType},
 };
@@ -14,7 +21,16 @@ pub struct BulletPlugin;
 
 impl Plugin for BulletPlugin {
     fn build(&self, app: &mut App) {
-        app.add_systems(Update, apply_bullet_damage);
+        app.add_systems(
+            Update,
+            (
+                apply_bullet_damage,
+                steer_homing_bullets,
+                expire_bullets,
+                despawn_offscreen_bullets,
+            )
+                .run_if(in_state(GameState::Playing)),
+        );
     }
 }
 
@@ -22,11 +38,107 @@ impl Plugin for BulletPlugin {
 #[require(Transform, Visibility)]
 pub struct Bullet;
 
+/// What a weapon fires, read from `weapons.toml`.
+#[derive(Component, Clone, Debug, Deserialize)]
+#[serde(default)]
+pub struct BulletType {
+    pub shape: BulletShape,
+    pub speed: f32,
+    pub damage: f32,
+    pub kind: DamageKind,
+    /// Distance from where it was fired before it despawns.
+    pub range: f32,
+    /// Seconds before it despawns, for bullets that slow down or stop.
+    pub lifetime: f32,
+    /// Number of targets it passes through, blocks always stop it.
+    pub pierce: u32,
+    /// Turn rate towards the closest target, in degrees per second.
+    pub homing: Option<f32>,
+}
+
+impl Default for BulletType {
+    fn default() -> Self {
+        Self {
+            shape: BulletShape::Arrow,
+            speed: 2000.,
+            damage: 10.,
+            kind: DamageKind::Kinetic,
+            range: 3000.,
+            lifetime: 2.,
+            pierce: 0,
+            homing: None,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum BulletShape {
+    #[default]
+    Arrow,
+    Needle,
+    Shell,
+    Missile,
+}
+
+impl BulletShape {
+    fn collider(&self) -> Collider {
+        match self {
+            BulletShape::Arrow => Collider::regular_polygon(17., 3),
+            BulletShape::Needle => Collider::rectangle(4., 40.),
+            BulletShape::Shell => Collider::circle(12.),
+            BulletShape::Missile => Collider::rectangle(12., 32.),
+        }
+    }
+
+    /// Outline pointing up, bullets are rotated so up is forward.
+    fn path(&self) -> Path {
+        let point = |degrees: f32, length: f32| {
+            Vec2::new(
+                degrees.to_radians().cos() * length,
+                degrees.to_radians().sin() * length,
+            )
+        };
+        match self {
+            BulletShape::Arrow => GeometryBuilder::build_as(&shapes::Polygon {
+                points: vec![point(330., 13.), point(90., 13.), point(210., 13.)],
+                closed: false,
+            }),
+            BulletShape::Needle => GeometryBuilder::build_as(&shapes::Rectangle {
+                extents: Vec2::new(2., 40.),
+                origin: RectangleOrigin::Center,
+                radii: None,
+            }),
+            BulletShape::Shell => GeometryBuilder::build_as(&shapes::Circle {
+                radius: 12.,
+                center: Vec2::ZERO,
+            }),
+            BulletShape::Missile => GeometryBuilder::build_as(&shapes::Polygon {
+                points: vec![
+                    Vec2::new(0., 18.),
+                    Vec2::new(6., -14.),
+                    Vec2::new(-6., -14.),
+                ],
+                closed: true,
+            }),
+        }
+    }
+}
+
+/// Targets already hit by a piercing bullet.
+#[derive(Component, Default)]
+struct BulletHits(Vec<Entity>);
+
 #[derive(Component)]
-pub enum BulletType {
-    Standard,
+pub struct BulletLifetime {
+    timer: Timer,
+    origin: Vec2,
+    range: f32,
 }
 
+/// How far outside the camera view bullets are despawned.
+const OFFSCREEN_MARGIN: f32 = 1000.;
+
 pub struct BulletProp {
     pub rotation: f32,
     pub position: Vec3,
@@ -37,16 +149,6 @@ pub struct BulletProp {
 impl Template for Bullet {
     type Prop = BulletProp;
     fn construct(mut cmd: EntityCommands<'_>, prop: Self::Prop) -> EntityCommands<'_> {
-        let point1 = Vec2::new(0., 13.);
-        let point2 = Vec2::new(
-            210f32.to_radians().cos() * 13.,
-            210f32.to_radians().sin() * 13.,
-        );
-        let point3 = Vec2::new(
-            330f32.to_radians().cos() * 13.,
-            330f32.to_radians().sin() * 13.,
-        );
-
         let BulletProp {
             rotation,
             position,
@@ -54,34 +156,34 @@ impl Template for Bullet {
             layers,
         } = prop;
 
+        let path = bullet_type.shape.path();
         cmd.insert((
             Bullet,
-            bullet_type,
+            Name::new("Bullet"),
+            StateScoped(InGame),
+            BulletLifetime {
+                timer: Timer::from_seconds(bullet_type.lifetime, TimerMode::Once),
+                origin: position.truncate(),
+                range: bullet_type.range,
+            },
+            BulletHits::default(),
             RigidBody::Kinematic,
             LinearVelocity(Vec2 {
-                x: rotation.cos() * 2000.,
-                y: rotation.sin() * 2000.,
+                x: rotation.cos() * bullet_type.speed,
+                y: rotation.sin() * bullet_type.speed,
             }),
             Transform::default()
                 .with_translation(position)
                 .with_rotation(Quat::from_rotation_z(rotation - PI / 2.)),
-            Collider::regular_polygon(17., 3),
+            bullet_type.shape.collider(),
             Sensor,
             layers,
             CollidingEntities::default(),
-            // CollisionLayers::new( GameLayer::Bullet,
-            //     [GameLayer::Default, GameLayer::Block, GameLayer::Enemy],
-            // ),
+            bullet_type,
         ))
         .with_children(|parent| {
             parent.spawn((
-                ShapeBundle {
-                    path: GeometryBuilder::build_as(&shapes::Polygon {
-                        points: vec![point3, point1, point2],
-                        closed: false,
-                    }),
-                    ..default()
-                },
+                ShapeBundle { path, ..default() },
                 Fill::color(Color::WHITE.with_alpha(0.)),
                 Stroke::new(Color::WHITE, 3.),
             ));
@@ -92,27 +194,59 @@ impl Template for Bullet {
 
 fn apply_bullet_damage(
     mut cmd: Commands,
-    bullets: Query<
-        (Entity, Ref<CollidingEntities>, &Transform),
+    mut bullets: Query<
+        (
+            Entity,
+            Ref<CollidingEntities>,
+            &Transform,
+            &BulletType,
+            &mut BulletHits,
+        ),
         (With<Bullet>, Changed<CollidingEntities>),
     >,
-    mut health: Query<&mut Health>,
+    health: Query<&Health>,
+    blocks: Query<(), With<Block>>,
+    mut damage: EventWriter<DamageEvent>,
     mut debug_log: ResMut<DebugLog>,
     mut rng: GlobalEntropy<WyRand>,
 ) {
-    for (id, entities, transform) in bullets.iter() {
+    for (id, entities, transform, bullet_type, mut hits) in bullets.iter_mut() {
         let mut should_despawn = false;
         for entity in entities.iter() {
-            debug_log.push(format!("Bullet collided with entity {:?}", entity));
-            if let Ok(mut health) = health.get_mut(*entity) {
-                debug_log.push(format!("Entity has health {:?}", health.0));
-                health.0 -= 10.;
-                should_despawn = true;
+            if hits.0.contains(entity) {
+                continue;
+            }
+            debug_log.log(
+                Level::Debug,
+                "bullet",
+                format!("Bullet collided with entity {:?}", entity),
+            );
+            if let Ok(health) = health.get(*entity) {
+                debug_log.log(
+                    Level::Debug,
+                    "bullet",
+                    format!("Entity has health {:?}", health.0),
+                );
+                damage.send(DamageEvent {
+                    target: *entity,
+                    source: Some(id),
+                    amount: bullet_type.damage,
+                    kind: bullet_type.kind,
+                });
+                hits.0.push(*entity);
+                should_despawn |= hits.0.len() > bullet_type.pierce as usize;
                 cmd.template::<Vfx>(VfxProp {
                     vfx_type: VfxType::Explosion,
                     position: transform.translation,
                     rng: rng.fork_rng(),
                 });
+            } else if blocks.contains(*entity) && !should_despawn {
+                should_despawn = true;
+                cmd.template::<Vfx>(VfxProp {
+                    vfx_type: VfxType::Impact,
+                    position: transform.translation,
+                    rng: rng.fork_rng(),
+                });
             }
         }
         if should_despawn {
@@ -120,3 +254,78 @@ fn apply_bullet_damage(
         }
     }
 }
+
+fn steer_homing_bullets(
+    mut bullets: Query<
+        (
+            &BulletType,
+            &CollisionLayers,
+            &mut LinearVelocity,
+            &mut Transform,
+        ),
+        With<Bullet>,
+    >,
+    targets: Query<(&GlobalTransform, &CollisionLayers), (With<Health>, Without<Dead>)>,
+    time: Res<Time>,
+) {
+    for (bullet_type, layers, mut linvel, mut transform) in bullets.iter_mut() {
+        let Some(turn_rate) = bullet_type.homing else {
+            continue;
+        };
+        let position = transform.translation.truncate();
+        let Some(target) = targets
+            .iter()
+            .filter(|(_, target_layers)| layers.interacts_with(**target_layers))
+            .map(|(target, _)| target.translation().truncate())
+            .min_by(|a, b| {
+                a.distance_squared(position)
+                    .total_cmp(&b.distance_squared(position))
+            })
+        else {
+            continue;
+        };
+        let angle = linvel.0.to_angle();
+        // Shortest way around, between -PI and PI
+        let diff = ((target - position).to_angle() - angle + PI).rem_euclid(TAU) - PI;
+        let max_turn = turn_rate.to_radians() * time.delta_secs();
+        let angle = angle + diff.clamp(-max_turn, max_turn);
+        linvel.0 = Vec2::from_angle(angle) * linvel.0.length();
+        transform.rotation = Quat::from_rotation_z(angle - PI / 2.);
+    }
+}
+
+fn expire_bullets(
+    mut cmd: Commands,
+    mut bullets: Query<(Entity, &mut BulletLifetime, &Transform)>,
+    time: Res<Time>,
+) {
+    for (id, mut lifetime, transform) in bullets.iter_mut() {
+        let finished = lifetime.timer.tick(time.delta()).finished();
+        if finished || transform.translation.truncate().distance(lifetime.origin) > lifetime.range {
+            cmd.entity(id).try_despawn_recursive();
+        }
+    }
+}
+
+fn despawn_offscreen_bullets(
+    mut cmd: Commands,
+    camera: Single<(&Camera, &GlobalTransform), With<Camera2d>>,
+    bullets: Query<(Entity, &Transform), With<Bullet>>,
+) {
+    let (camera, camera_transform) = camera.into_inner();
+    let Some(viewport) = camera.logical_viewport_rect() else {
+        return;
+    };
+    let (Ok(a), Ok(b)) = (
+        camera.viewport_to_world_2d(camera_transform, viewport.min),
+        camera.viewport_to_world_2d(camera_transform, viewport.max),
+    ) else {
+        return;
+    };
+    let bounds = Rect::from_corners(a, b).inflate(OFFSCREEN_MARGIN);
+    for (id, transform) in bullets.iter() {
+        if !bounds.contains(transform.translation.truncate()) {
+            cmd.entity(id).try_despawn_recursive();
+        }
+    }
+}
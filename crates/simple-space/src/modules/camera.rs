@@ -0,0 +1,259 @@
+use avian2d::prelude::*;
+use bevy::{
+    input::mouse::{MouseScrollUnit, MouseWheel},
+    picking::{focus::HoverMap, pointer::PointerId},
+    prelude::*,
+    transform::TransformSystem,
+};
+use bevy_vector_shapes::prelude::*;
+use playground_core::CursorPosition;
+use playground_ui::no_input_focused;
+
+use super::{ability::Invulnerable, damage::DamageEvent, game_state::InGame, health::Dead};
+
+pub struct CameraRigPlugin;
+
+impl Plugin for CameraRigPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<WorldBounds>()
+            .add_systems(Startup, setup)
+            .add_systems(
+                Update,
+                (
+                    zoom_camera.run_if(no_input_focused),
+                    draw_bounds.run_if(in_state(InGame)),
+                ),
+            )
+            .add_systems(
+                PostUpdate,
+                (follow_target, shake_camera)
+                    .chain()
+                    .before(TransformSystem::TransformPropagate),
+            )
+            .add_observer(add_trauma)
+            .add_observer(shake_on_damage)
+            .add_observer(snap_to_target);
+    }
+}
+
+/// The camera follows this entity.
+#[derive(Component)]
+pub struct CameraTarget;
+
+/// Play area, the camera never shows what's outside of it.
+#[derive(Resource)]
+pub struct WorldBounds(pub Rect);
+
+impl Default for WorldBounds {
+    fn default() -> Self {
+        Self(Rect::from_center_half_size(Vec2::ZERO, Vec2::splat(6000.)))
+    }
+}
+
+#[derive(Component)]
+pub struct CameraRig {
+    /// How quickly the camera catches up, higher is snappier.
+    pub follow_speed: f32,
+    /// Fraction of the way toward the cursor the camera leans.
+    pub cursor_look_ahead: f32,
+    /// Seconds of the target's velocity the camera leans ahead.
+    pub velocity_look_ahead: f32,
+    pub max_look_ahead: f32,
+    /// Target projection scale, changed with the mouse wheel.
+    pub zoom: f32,
+    pub min_zoom: f32,
+    pub max_zoom: f32,
+    /// Where the camera looks, before the shake.
+    center: Vec2,
+    /// Cursor relative to the target, kept while the cursor is over the UI.
+    cursor_offset: Vec2,
+}
+
+impl Default for CameraRig {
+    fn default() -> Self {
+        Self {
+            follow_speed: 5.,
+            cursor_look_ahead: 0.25,
+            velocity_look_ahead: 0.3,
+            max_look_ahead: 600.,
+            zoom: 3.,
+            min_zoom: 1.5,
+            max_zoom: 6.,
+            center: Vec2::ZERO,
+            cursor_offset: Vec2::ZERO,
+        }
+    }
+}
+
+/// Trauma-based screen shake, the offset grows with the square of `trauma`
+/// which wears off over time.
+#[derive(Component)]
+pub struct CameraShake {
+    pub trauma: f32,
+    /// Trauma lost per second.
+    pub decay: f32,
+    pub max_offset: f32,
+    /// Radians.
+    pub max_angle: f32,
+}
+
+impl Default for CameraShake {
+    fn default() -> Self {
+        Self {
+            trauma: 0.,
+            decay: 1.5,
+            max_offset: 60.,
+            max_angle: 0.05,
+        }
+    }
+}
+
+/// Trigger to shake the camera, trauma adds up to 1.
+#[derive(Event)]
+pub struct Shake(pub f32);
+
+fn setup(mut cmd: Commands) {
+    let rig = CameraRig::default();
+    cmd.spawn((
+        Camera2d,
+        OrthographicProjection {
+            scale: rig.zoom,
+            ..OrthographicProjection::default_2d()
+        },
+        Camera {
+            clear_color: ClearColorConfig::Custom(Color::BLACK),
+            ..default()
+        },
+        rig,
+        CameraShake::default(),
+    ));
+}
+
+/// Whether the mouse is over a UI node, e.g. a panel it could be scrolling.
+fn cursor_over_ui(hover_map: &HoverMap, nodes: &Query<(), With<Node>>) -> bool {
+    hover_map
+        .get(&PointerId::Mouse)
+        .is_some_and(|hovered| hovered.keys().any(|entity| nodes.contains(*entity)))
+}
+
+fn zoom_camera(
+    mut wheel: EventReader<MouseWheel>,
+    mut camera: Single<(&mut CameraRig, &mut OrthographicProjection)>,
+    hover_map: Res<HoverMap>,
+    nodes: Query<(), With<Node>>,
+    time: Res<Time>,
+) {
+    let (rig, projection) = &mut *camera;
+    // The wheel scrolls the UI under the cursor instead
+    let over_ui = cursor_over_ui(&hover_map, &nodes);
+    for event in wheel.read() {
+        if over_ui {
+            continue;
+        }
+        let lines = match event.unit {
+            MouseScrollUnit::Line => event.y,
+            MouseScrollUnit::Pixel => event.y / 50.,
+        };
+        rig.zoom = (rig.zoom * 0.9f32.powf(lines)).clamp(rig.min_zoom, rig.max_zoom);
+    }
+    let t = 1. - (-10. * time.delta_secs()).exp();
+    projection.scale = projection.scale.lerp(rig.zoom, t);
+}
+
+fn follow_target(
+    camera: Single<(&mut CameraRig, &OrthographicProjection, &Camera)>,
+    target: Option<
+        Single<(&Transform, Option<&LinearVelocity>), (With<CameraTarget>, Without<Dead>)>,
+    >,
+    cursor: Res<CursorPosition>,
+    hover_map: Res<HoverMap>,
+    nodes: Query<(), With<Node>>,
+    bounds: Res<WorldBounds>,
+    time: Res<Time>,
+) {
+    let (mut rig, projection, camera) = camera.into_inner();
+    // Without a living target the camera holds still
+    if let Some(target) = target {
+        let (transform, velocity) = target.into_inner();
+        let position = transform.translation.truncate();
+        // Moving the cursor across panels shouldn't swing the view around
+        if !cursor_over_ui(&hover_map, &nodes) {
+            rig.cursor_offset = cursor.world - position;
+        }
+        let look_ahead = rig.cursor_offset * rig.cursor_look_ahead
+            + velocity.map_or(Vec2::ZERO, |velocity| velocity.0 * rig.velocity_look_ahead);
+        let desired = position + look_ahead.clamp_length_max(rig.max_look_ahead);
+        let t = 1. - (-rig.follow_speed * time.delta_secs()).exp();
+        rig.center = rig.center.lerp(desired, t);
+    }
+
+    let Some(viewport) = camera.logical_viewport_size() else {
+        return;
+    };
+    let half_view = viewport * projection.scale / 2.;
+    let (min, max) = (bounds.0.min + half_view, bounds.0.max - half_view);
+    // A view bigger than the play area stays centered on it
+    rig.center = Vec2::new(
+        if min.x <= max.x {
+            rig.center.x.clamp(min.x, max.x)
+        } else {
+            bounds.0.center().x
+        },
+        if min.y <= max.y {
+            rig.center.y.clamp(min.y, max.y)
+        } else {
+            bounds.0.center().y
+        },
+    );
+}
+
+fn shake_camera(camera: Single<(&mut Transform, &CameraRig, &mut CameraShake)>, time: Res<Time>) {
+    let (mut transform, rig, mut shake) = camera.into_inner();
+    shake.trauma = (shake.trauma - shake.decay * time.delta_secs()).max(0.);
+    let amount = shake.trauma * shake.trauma;
+    // Overlapping sines instead of random jumps so the shake stays smooth
+    let t = time.elapsed_secs() * 30.;
+    let noise = |seed: f32| ((t + seed).sin() + (t * 1.7 + seed * 3.).sin()) / 2.;
+    let offset = Vec2::new(noise(0.), noise(10.)) * shake.max_offset * amount;
+    transform.translation.x = rig.center.x + offset.x;
+    transform.translation.y = rig.center.y + offset.y;
+    transform.rotation = Quat::from_rotation_z(noise(20.) * shake.max_angle * amount);
+}
+
+fn add_trauma(trigger: Trigger<Shake>, mut shake: Query<&mut CameraShake>) {
+    for mut shake in shake.iter_mut() {
+        shake.trauma = (shake.trauma + trigger.0).min(1.);
+    }
+}
+
+fn shake_on_damage(
+    trigger: Trigger<DamageEvent>,
+    mut cmd: Commands,
+    target: Query<(), (With<CameraTarget>, Without<Invulnerable>)>,
+) {
+    if trigger.amount > 0. && target.contains(trigger.entity()) {
+        cmd.trigger(Shake((trigger.amount / 50.).min(0.6)));
+    }
+}
+
+/// Jumps to a new target instead of panning across the map.
+fn snap_to_target(
+    trigger: Trigger<OnAdd, CameraTarget>,
+    target: Query<&Transform>,
+    mut rig: Query<&mut CameraRig>,
+) {
+    let Ok(transform) = target.get(trigger.entity()) else {
+        return;
+    };
+    for mut rig in rig.iter_mut() {
+        rig.center = transform.translation.truncate();
+    }
+}
+
+fn draw_bounds(mut painter: ShapePainter, bounds: Res<WorldBounds>) {
+    painter.transform = Transform::from_translation(bounds.0.center().extend(0.));
+    painter.hollow = true;
+    painter.thickness = 6.;
+    painter.color = Color::WHITE.with_alpha(0.3);
+    painter.rect(bounds.0.size());
+}
@@ -0,0 +1,152 @@
+use bevy::prelude::*;
+use serde::Deserialize;
+
+use super::{game_state::GameState, health::Health};
+
+pub struct DamagePlugin;
+
+impl Plugin for DamagePlugin {
+    fn build(&self, app: &mut App) {
+        app.register_type::<Resistances>()
+            .register_type::<Armor>()
+            .register_type::<Shield>()
+            .add_event::<DamageEvent>()
+            .add_systems(Update, process_damage)
+            .add_systems(Update, regen_shields.run_if(in_state(GameState::Playing)));
+    }
+}
+
+#[derive(Reflect, Clone, Copy, PartialEq, Eq, Debug, Default, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DamageKind {
+    #[default]
+    Kinetic,
+    Explosive,
+    Energy,
+}
+
+/// Send it to hurt `target`. Before it's applied it's triggered on `target`,
+/// observers can change it through `Trigger::event_mut` and cancel it with
+/// [`DamageEvent::cancel`]. Then [`Resistances`], [`Armor`] and [`Shield`]
+/// reduce it, whatever is left comes off [`Health`].
+#[derive(Event, Clone, Copy, Debug)]
+pub struct DamageEvent {
+    pub target: Entity,
+    pub source: Option<Entity>,
+    pub amount: f32,
+    pub kind: DamageKind,
+}
+
+impl DamageEvent {
+    pub fn cancel(&mut self) {
+        self.amount = 0.;
+    }
+}
+
+/// Fraction of each kind of damage that is ignored, 1 is immune.
+#[derive(Component, Reflect, Default, Clone, Copy)]
+#[reflect(Component)]
+pub struct Resistances {
+    pub kinetic: f32,
+    pub explosive: f32,
+    pub energy: f32,
+}
+
+impl Resistances {
+    pub fn get(&self, kind: DamageKind) -> f32 {
+        match kind {
+            DamageKind::Kinetic => self.kinetic,
+            DamageKind::Explosive => self.explosive,
+            DamageKind::Energy => self.energy,
+        }
+    }
+}
+
+/// Flat reduction of every kinetic and explosive hit, energy goes through.
+#[derive(Component, Reflect, Default, Clone, Copy)]
+#[reflect(Component)]
+pub struct Armor(pub f32);
+
+/// Absorbs damage before [`Health`] and recharges after not being hit for
+/// `delay` seconds.
+#[derive(Component, Reflect, Clone, Copy)]
+#[reflect(Component)]
+pub struct Shield {
+    pub current: f32,
+    pub max: f32,
+    /// Per second.
+    pub regen: f32,
+    pub delay: f32,
+    since_hit: f32,
+}
+
+impl Shield {
+    pub fn new(max: f32) -> Self {
+        Self {
+            current: max,
+            max,
+            regen: 0.,
+            delay: 0.,
+            since_hit: 0.,
+        }
+    }
+
+    pub fn with_regen(mut self, regen: f32, delay: f32) -> Self {
+        self.regen = regen;
+        self.delay = delay;
+        self
+    }
+
+    /// Returns the damage that got through.
+    fn absorb(&mut self, amount: f32) -> f32 {
+        self.since_hit = 0.;
+        let absorbed = amount.min(self.current);
+        self.current -= absorbed;
+        amount - absorbed
+    }
+}
+
+fn process_damage(mut cmd: Commands, mut events: EventReader<DamageEvent>) {
+    for event in events.read().copied() {
+        cmd.queue(move |world: &mut World| apply_damage(world, event));
+    }
+}
+
+fn apply_damage(world: &mut World, mut event: DamageEvent) {
+    if world.get_entity(event.target).is_err() {
+        return;
+    }
+    world.trigger_targets_ref(&mut event, event.target);
+    let Ok(mut target) = world.get_entity_mut(event.target) else {
+        return;
+    };
+
+    let mut amount = event.amount;
+    if let Some(resistances) = target.get::<Resistances>() {
+        amount *= 1. - resistances.get(event.kind).clamp(0., 1.);
+    }
+    if event.kind != DamageKind::Energy {
+        amount -= target.get::<Armor>().map_or(0., |armor| armor.0);
+    }
+    if amount <= 0. {
+        return;
+    }
+    if let Some(mut shield) = target.get_mut::<Shield>() {
+        amount = shield.absorb(amount);
+    }
+    if amount <= 0. {
+        return;
+    }
+    if let Some(mut health) = target.get_mut::<Health>() {
+        health.0 -= amount;
+    }
+}
+
+fn regen_shields(mut shields: Query<&mut Shield>, time: Res<Time>) {
+    for mut shield in shields.iter_mut() {
+        shield.since_hit += time.delta_secs();
+        if shield.since_hit >= shield.delay && shield.current < shield.max {
+            shield.current = (shield.current + shield.regen * time.delta_secs()).min(shield.max);
+        }
+    }
+}
@@ -0,0 +1,172 @@
+use avian2d::prelude::*;
+use bevy::prelude::*;
+use bevy_rand::{global::GlobalEntropy, prelude::WyRand, traits::ForkableRng};
+
+use super::{
+    ability::Invulnerable,
+    camera::Shake,
+    game_state::GameState,
+    health::{Dead, Died, Health, MaxHealth},
+    lives::Lives,
+    physics::GameLayer,
+    pickup::{Pickup, PickupProp},
+    template::TemplateExt,
+    vfx::{Vfx, VfxProp, VfxType},
+};
+
+pub struct DeathPlugin;
+
+impl Plugin for DeathPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(Update, respawn.run_if(in_state(GameState::Playing)))
+            .add_observer(apply_death_effects);
+    }
+}
+
+/// What happens when the entity [`Died`], in order. Entities without it are
+/// only marked [`Dead`].
+///
+/// ```ignore
+/// OnDeath(vec![DeathEffect::Explosion, DeathEffect::Despawn])
+/// ```
+#[derive(Component, Clone)]
+pub struct OnDeath(pub Vec<DeathEffect>);
+
+#[derive(Clone)]
+pub enum DeathEffect {
+    Despawn,
+    Explosion,
+    DropLoot(Pickup),
+    /// Hides the entity and brings it back near `position` with full health,
+    /// away from enemies and blocks. An entity with [`Lives`] spends one and
+    /// is despawned instead once they run out.
+    Respawn {
+        delay: f32,
+        position: Vec2,
+        /// Seconds of [`Invulnerable`] after coming back.
+        invulnerability: f32,
+    },
+}
+
+#[derive(Component)]
+struct Respawning {
+    timer: Timer,
+    position: Vec2,
+    invulnerability: f32,
+}
+
+fn apply_death_effects(
+    trigger: Trigger<Died>,
+    mut cmd: Commands,
+    mut on_death: Query<(&OnDeath, &GlobalTransform, Option<&mut Lives>)>,
+    mut rng: GlobalEntropy<WyRand>,
+) {
+    let id = trigger.entity();
+    let Ok((OnDeath(effects), transform, mut lives)) = on_death.get_mut(id) else {
+        return;
+    };
+    for effect in effects {
+        match effect {
+            DeathEffect::Despawn => cmd.entity(id).despawn_recursive(),
+            DeathEffect::Explosion => {
+                cmd.template::<Vfx>(VfxProp {
+                    vfx_type: VfxType::Explosion,
+                    position: transform.translation(),
+                    rng: rng.fork_rng(),
+                });
+                cmd.trigger(Shake(0.4));
+            }
+            DeathEffect::DropLoot(pickup) => {
+                cmd.template::<Pickup>(PickupProp {
+                    pickup: pickup.clone(),
+                    position: transform.translation().truncate(),
+                });
+            }
+            DeathEffect::Respawn {
+                delay,
+                position,
+                invulnerability,
+            } => {
+                if let Some(lives) = lives.as_mut() {
+                    lives.0 = lives.0.saturating_sub(1);
+                    if lives.0 == 0 {
+                        cmd.entity(id).despawn_recursive();
+                        continue;
+                    }
+                }
+                cmd.entity(id).insert((
+                    Respawning {
+                        timer: Timer::from_seconds(*delay, TimerMode::Once),
+                        position: *position,
+                        invulnerability: *invulnerability,
+                    },
+                    Visibility::Hidden,
+                    RigidBodyDisabled,
+                    ColliderDisabled,
+                ));
+            }
+        }
+    }
+}
+
+fn respawn(
+    mut cmd: Commands,
+    mut respawning: Query<(
+        Entity,
+        &mut Respawning,
+        &mut Health,
+        &MaxHealth,
+        &mut Position,
+        &mut LinearVelocity,
+    )>,
+    spatial_query: SpatialQuery,
+    time: Res<Time>,
+) {
+    for (id, mut respawning, mut health, max_health, mut position, mut linvel) in
+        respawning.iter_mut()
+    {
+        if !respawning.timer.tick(time.delta()).just_finished() {
+            continue;
+        }
+        health.0 = max_health.0;
+        position.0 = safe_spawn_point(&spatial_query, respawning.position);
+        linvel.0 = Vec2::ZERO;
+        cmd.entity(id)
+            .remove::<(Respawning, Dead, RigidBodyDisabled, ColliderDisabled)>()
+            .insert(Visibility::Inherited);
+        if respawning.invulnerability > 0. {
+            cmd.entity(id)
+                .insert(Invulnerable::new(respawning.invulnerability));
+        }
+    }
+}
+
+/// Nothing dangerous may be closer than this to a spawn point.
+const SAFE_RADIUS: f32 = 300.;
+
+/// `preferred` if it's clear, otherwise the closest clear point on rings
+/// around it.
+fn safe_spawn_point(spatial_query: &SpatialQuery, preferred: Vec2) -> Vec2 {
+    let area = Collider::circle(SAFE_RADIUS);
+    let filter =
+        SpatialQueryFilter::from_mask([GameLayer::Default, GameLayer::Block, GameLayer::Enemy]);
+    let is_clear = |point: Vec2| {
+        spatial_query
+            .shape_intersections(&area, point, 0., &filter)
+            .is_empty()
+    };
+    if is_clear(preferred) {
+        return preferred;
+    }
+    for ring in 1..=10 {
+        let distance = ring as f32 * SAFE_RADIUS;
+        for step in 0..8 {
+            let angle = step as f32 * std::f32::consts::TAU / 8.;
+            let point = preferred + Vec2::from_angle(angle) * distance;
+            if is_clear(point) {
+                return point;
+            }
+        }
+    }
+    preferred
+}
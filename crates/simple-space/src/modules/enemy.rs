@@ -1,11 +1,17 @@
 use avian2d::{math::TAU, prelude::*};
 use bevy::prelude::*;
 use bevy_prototype_lyon::prelude::*;
+use playground_ui::{ButtonPressed, ConsoleAppExt, ConsoleArgs, ConsoleResult};
 
 use super::{
+    ability::Dash,
+    damage::{Armor, DamageEvent},
+    death::{DeathEffect, OnDeath},
+    game_state::InGame,
     health::{HPBarConfig, Health},
     physics::GameLayer,
-    ship::MaxSpeed,
+    pickup::Pickup,
+    ship::{MaxSpeed, Ship},
     template::{Template, TemplateExt},
 };
 
@@ -13,10 +19,59 @@ pub struct EnemyPlugin;
 
 impl Plugin for EnemyPlugin {
     fn build(&self, app: &mut App) {
-        app.add_systems(Startup, setup);
+        app.add_systems(OnEnter(InGame), setup)
+            .register_console_command("spawn_enemy", "spawn_enemy [x] [y]", spawn_enemy)
+            .add_observer(spawn_enemy_button)
+            .add_observer(dodge_when_hit);
     }
 }
 
+fn spawn_enemy_button(
+    trigger: Trigger<ButtonPressed>,
+    mut cmd: Commands,
+    name: Query<&Name>,
+    in_game: Option<Res<State<InGame>>>,
+) {
+    if in_game.is_some()
+        && name
+            .get(trigger.entity())
+            .is_ok_and(|name| name.as_str() == "spawn_enemy")
+    {
+        cmd.template::<Enemy>(());
+    }
+}
+
+fn spawn_enemy(
+    In(args): In<ConsoleArgs>,
+    mut cmd: Commands,
+    in_game: Option<Res<State<InGame>>>,
+) -> ConsoleResult {
+    if in_game.is_none() {
+        return Err("no game is running".to_string());
+    }
+    let x = args.get_or(0, 300.)?;
+    let y = args.get_or(1, -300.)?;
+    cmd.template::<Enemy>(())
+        .insert(Transform::from_xyz(x, y, 0.));
+    Ok(format!("spawned enemy at {}, {}", x, y))
+}
+
+/// Dashes sideways out of the ship's line of fire when hurt.
+fn dodge_when_hit(
+    trigger: Trigger<DamageEvent>,
+    mut enemy: Query<(&mut Dash, &GlobalTransform), (With<Enemy>, Without<Ship>)>,
+    ship: Option<Single<&GlobalTransform, With<Ship>>>,
+) {
+    let Ok((mut dash, transform)) = enemy.get_mut(trigger.entity()) else {
+        return;
+    };
+    let Some(ship) = ship else {
+        return;
+    };
+    let away = (transform.translation() - ship.translation()).truncate();
+    dash.activate(away.perp());
+}
+
 fn setup(mut cmd: Commands) {
     cmd.template::<Enemy>(());
 }
@@ -30,13 +85,23 @@ impl Template for Enemy {
     fn construct(mut cmd: EntityCommands<'_>, _: Self::Prop) -> EntityCommands<'_> {
         cmd.insert((
             Enemy,
+            Name::new("Enemy"),
+            StateScoped(InGame),
             Health(100.),
+            Armor(2.),
             MaxSpeed(1000.),
+            Dash::new(1500., 3.),
+            LinearDamping(2.),
             RigidBody::Dynamic,
             GravityScale(0.),
             LockedAxes::ROTATION_LOCKED,
             Transform::from_xyz(300., -300., 0.),
             HPBarConfig { y_offset: 75. },
+            OnDeath(vec![
+                DeathEffect::Explosion,
+                DeathEffect::DropLoot(Pickup::Heal(25.)),
+                DeathEffect::Despawn,
+            ]),
             Collider::regular_polygon(60., 6),
             CollisionLayers::new(
                 GameLayer::Enemy,
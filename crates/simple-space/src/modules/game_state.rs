@@ -0,0 +1,199 @@
+use avian2d::prelude::*;
+use bevy::prelude::*;
+use playground_ui::{
+    ButtonPressed, ButtonShortcut, KeyBindingsAppExt, TextButton, action_just_pressed,
+    no_input_focused,
+};
+
+use super::ship::Ship;
+
+pub struct GameStatePlugin;
+
+impl Plugin for GameStatePlugin {
+    fn build(&self, app: &mut App) {
+        app.init_state::<GameState>()
+            .add_computed_state::<InGame>()
+            .enable_state_scoped_entities::<GameState>()
+            .enable_state_scoped_entities::<InGame>()
+            .register_action("pause", [KeyCode::Escape, KeyCode::KeyP])
+            .add_systems(OnEnter(GameState::MainMenu), spawn_main_menu)
+            .add_systems(OnEnter(GameState::Paused), (spawn_pause_menu, pause_time))
+            .add_systems(OnExit(GameState::Paused), unpause_time)
+            .add_systems(OnEnter(GameState::GameOver), spawn_game_over)
+            .add_systems(
+                Update,
+                (
+                    toggle_pause.run_if(
+                        action_just_pressed("pause")
+                            .and(no_input_focused)
+                            .and(in_state(GameState::Playing).or(in_state(GameState::Paused))),
+                    ),
+                    check_game_over.run_if(in_state(GameState::Playing)),
+                ),
+            )
+            .add_observer(restart);
+    }
+}
+
+#[derive(States, Default, Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub enum GameState {
+    #[default]
+    MainMenu,
+    Playing,
+    Paused,
+    GameOver,
+}
+
+/// A game is running, paused or just lost. The world is spawned on
+/// `OnEnter(InGame)` and entities with `StateScoped(InGame)` are despawned
+/// when going back to the main menu.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub struct InGame;
+
+impl ComputedStates for InGame {
+    type SourceStates = GameState;
+
+    fn compute(state: GameState) -> Option<Self> {
+        match state {
+            GameState::MainMenu => None,
+            GameState::Playing | GameState::Paused | GameState::GameOver => Some(InGame),
+        }
+    }
+}
+
+/// Despawns the world and spawns a fresh one without leaving [`InGame`].
+#[derive(Event)]
+pub struct Restart;
+
+fn restart(
+    _: Trigger<Restart>,
+    mut cmd: Commands,
+    scoped: Query<Entity, With<StateScoped<InGame>>>,
+    mut next_state: ResMut<NextState<GameState>>,
+) {
+    for entity in scoped.iter() {
+        cmd.entity(entity).despawn_recursive();
+    }
+    // Staying in InGame doesn't run its OnEnter again
+    cmd.queue(|world: &mut World| world.run_schedule(OnEnter(InGame)));
+    next_state.set(GameState::Playing);
+}
+
+fn toggle_pause(state: Res<State<GameState>>, mut next_state: ResMut<NextState<GameState>>) {
+    match state.get() {
+        GameState::Playing => next_state.set(GameState::Paused),
+        GameState::Paused => next_state.set(GameState::Playing),
+        _ => {}
+    }
+}
+
+/// Freezes every gameplay timer, not only physics: systems reading
+/// `Res<Time>` in `Update` see zero deltas until the game is resumed.
+fn pause_time(mut time: ResMut<Time<Virtual>>, mut physics_time: ResMut<Time<Physics>>) {
+    time.pause();
+    physics_time.pause();
+}
+
+fn unpause_time(mut time: ResMut<Time<Virtual>>, mut physics_time: ResMut<Time<Physics>>) {
+    time.unpause();
+    physics_time.unpause();
+}
+
+/// Seconds the last explosion plays before the game over menu.
+const GAME_OVER_DELAY: f32 = 2.;
+
+/// The ship is only despawned once it's out of lives.
+fn check_game_over(
+    ship: Query<(), With<Ship>>,
+    mut next_state: ResMut<NextState<GameState>>,
+    mut since_lost: Local<f32>,
+    time: Res<Time>,
+) {
+    if !ship.is_empty() {
+        *since_lost = 0.;
+        return;
+    }
+    *since_lost += time.delta_secs();
+    if *since_lost >= GAME_OVER_DELAY {
+        *since_lost = 0.;
+        next_state.set(GameState::GameOver);
+    }
+}
+
+fn menu<'a>(cmd: &'a mut Commands, state: GameState, title: &str) -> EntityCommands<'a> {
+    let mut menu = cmd.spawn((
+        StateScoped(state),
+        Node {
+            position_type: PositionType::Absolute,
+            width: Val::Percent(100.),
+            height: Val::Percent(100.),
+            flex_direction: FlexDirection::Column,
+            justify_content: JustifyContent::Center,
+            align_items: AlignItems::Center,
+            row_gap: Val::Px(8.),
+            ..default()
+        },
+        BackgroundColor(Color::BLACK.with_alpha(0.5)),
+        GlobalZIndex(1),
+    ));
+    menu.with_child((Text::new(title), TextFont::from_font_size(32.)));
+    menu
+}
+
+fn spawn_main_menu(mut cmd: Commands) {
+    menu(&mut cmd, GameState::MainMenu, "Simple Space").with_children(|parent| {
+        parent
+            .spawn((
+                TextButton::new("Play (Enter)"),
+                ButtonShortcut(KeyCode::Enter),
+            ))
+            .observe(
+                |_: Trigger<ButtonPressed>, mut next_state: ResMut<NextState<GameState>>| {
+                    next_state.set(GameState::Playing);
+                },
+            );
+    });
+}
+
+fn spawn_pause_menu(mut cmd: Commands) {
+    menu(&mut cmd, GameState::Paused, "Paused").with_children(|parent| {
+        parent.spawn(TextButton::new("Resume (Esc)")).observe(
+            |_: Trigger<ButtonPressed>, mut next_state: ResMut<NextState<GameState>>| {
+                next_state.set(GameState::Playing);
+            },
+        );
+        spawn_restart_button(parent);
+        spawn_main_menu_button(parent);
+    });
+}
+
+fn spawn_game_over(mut cmd: Commands) {
+    menu(&mut cmd, GameState::GameOver, "Game Over").with_children(|parent| {
+        spawn_restart_button(parent);
+        spawn_main_menu_button(parent);
+    });
+}
+
+fn spawn_restart_button(parent: &mut ChildBuilder) {
+    parent
+        .spawn((
+            TextButton::new("Restart (R)"),
+            ButtonShortcut(KeyCode::KeyR),
+        ))
+        .observe(|_: Trigger<ButtonPressed>, mut cmd: Commands| {
+            cmd.trigger(Restart);
+        });
+}
+
+fn spawn_main_menu_button(parent: &mut ChildBuilder) {
+    parent
+        .spawn((
+            TextButton::new("Main menu (M)"),
+            ButtonShortcut(KeyCode::KeyM),
+        ))
+        .observe(
+            |_: Trigger<ButtonPressed>, mut next_state: ResMut<NextState<GameState>>| {
+                next_state.set(GameState::MainMenu);
+            },
+        );
+}
@@ -6,17 +6,39 @@ pub struct HealthPlugin;
 
 impl Plugin for HealthPlugin {
     fn build(&self, app: &mut App) {
-        app.add_systems(Startup, setup)
-            .add_systems(Update, (sync_hpbar_position, sync_health_hpbar));
+        app.register_type::<Health>()
+            .register_type::<MaxHealth>()
+            .add_systems(Startup, setup)
+            .add_systems(
+                Update,
+                (
+                    detect_death,
+                    sync_hpbar_position,
+                    sync_health_hpbar,
+                    sync_hpbar_visibility,
+                ),
+            )
+            .add_observer(despawn_hpbar);
     }
 }
 
-#[derive(Component)]
+#[derive(Component, Reflect)]
+#[reflect(Component)]
 pub struct Health(pub f32);
 
-#[derive(Component, Default)]
+#[derive(Component, Default, Reflect)]
+#[reflect(Component)]
 pub struct MaxHealth(pub f32);
 
+/// Triggered on an entity when its [`Health`] drops to zero or below.
+#[derive(Event)]
+pub struct Died;
+
+/// Marks an entity that [`Died`] and hasn't been revived, it won't die again
+/// until this is removed.
+#[derive(Component)]
+pub struct Dead;
+
 #[derive(Component)]
 #[require(Transform, Visibility)]
 pub struct HPBar;
@@ -26,7 +48,7 @@ pub struct HPBarConfig {
     pub y_offset: f32,
 }
 
-#[derive(Component)]
+#[derive(Component, Clone, Copy)]
 struct HPBarRef(Entity);
 
 #[derive(Component)]
@@ -96,6 +118,27 @@ fn setup(world: &mut World) {
         });
 }
 
+fn despawn_hpbar(trigger: Trigger<OnRemove, HPBarRef>, mut cmd: Commands, hpbar: Query<&HPBarRef>) {
+    let Ok(HPBarRef(hpbar)) = hpbar.get(trigger.entity()) else {
+        return;
+    };
+    if let Some(mut hpbar) = cmd.get_entity(*hpbar) {
+        hpbar.try_despawn_recursive();
+    }
+}
+
+fn detect_death(
+    mut cmd: Commands,
+    health: Query<(Entity, &Health), (Changed<Health>, Without<Dead>)>,
+) {
+    for (id, health) in health.iter() {
+        if health.0 <= 0. {
+            cmd.entity(id).insert(Dead);
+            cmd.trigger_targets(Died, id);
+        }
+    }
+}
+
 fn sync_hpbar_position(
     owner_position: Query<(Ref<Position>, &HPBarRef), (Changed<Position>, With<Health>)>,
     mut hp_bar: Query<&mut Transform, With<HPBar>>,
@@ -130,3 +173,14 @@ fn sync_health_hpbar(
         }
     }
 }
+
+fn sync_hpbar_visibility(
+    owner: Query<(&Visibility, &HPBarRef), Changed<Visibility>>,
+    mut hp_bar: Query<&mut Visibility, (With<HPBar>, Without<HPBarRef>)>,
+) {
+    for (visibility, HPBarRef(hpbar_id)) in owner.iter() {
+        if let Ok(mut hp_bar_visibility) = hp_bar.get_mut(*hpbar_id) {
+            hp_bar_visibility.set_if_neq(*visibility);
+        }
+    }
+}
@@ -0,0 +1,50 @@
+use bevy::prelude::*;
+
+use super::{game_state::InGame, ship::Ship};
+
+pub struct LivesPlugin;
+
+impl Plugin for LivesPlugin {
+    fn build(&self, app: &mut App) {
+        app.register_type::<Lives>()
+            .add_systems(OnEnter(InGame), spawn_lives_counter)
+            .add_systems(Update, update_lives_counter.run_if(in_state(InGame)));
+    }
+}
+
+/// Tries left including the current one, spent by
+/// [`DeathEffect::Respawn`](super::death::DeathEffect::Respawn).
+#[derive(Component, Reflect, Clone, Copy)]
+#[reflect(Component)]
+pub struct Lives(pub u32);
+
+#[derive(Component)]
+struct LivesCounter;
+
+fn spawn_lives_counter(mut cmd: Commands) {
+    cmd.spawn((
+        LivesCounter,
+        StateScoped(InGame),
+        Text::default(),
+        TextFont::from_font_size(24.),
+        Node {
+            position_type: PositionType::Absolute,
+            top: Val::Px(12.),
+            right: Val::Px(16.),
+            ..default()
+        },
+    ));
+}
+
+fn update_lives_counter(
+    ship: Query<&Lives, With<Ship>>,
+    mut counter: Query<&mut Text, With<LivesCounter>>,
+) {
+    let lives = ship.iter().next().map_or(0, |lives| lives.0);
+    let text = format!("Lives: {}", lives);
+    for mut counter in counter.iter_mut() {
+        if counter.0 != text {
+            counter.0 = text.clone();
+        }
+    }
+}
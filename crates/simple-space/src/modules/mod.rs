@@ -1,16 +1,36 @@
+mod ability;
+mod ammo;
 mod asteroid;
 mod block;
 mod bullet;
+mod camera;
+mod damage;
+mod death;
 mod enemy;
+mod game_state;
 mod health;
+mod lives;
 mod physics;
+mod pickup;
 mod ship;
 mod template;
 mod vfx;
+mod weapon;
 
+pub use ability::{AbilityPlugin, Boost, Dash, Dashing, Invulnerable};
+pub use ammo::{Ammo, AmmoPlugin, AmmoState, EnergyPool};
 pub use asteroid::AsteroidPlugin;
-pub use bullet::BulletPlugin;
+pub use bullet::{Bullet, BulletPlugin, BulletProp, BulletType};
+pub use camera::{CameraRig, CameraRigPlugin, CameraShake, CameraTarget, Shake, WorldBounds};
+pub use damage::{Armor, DamageEvent, DamageKind, DamagePlugin, Resistances, Shield};
+pub use death::{DeathEffect, DeathPlugin, OnDeath};
 pub use enemy::EnemyPlugin;
-pub use health::HealthPlugin;
-pub use ship::ShipPlugin;
+pub use game_state::{GameState, GameStatePlugin};
+pub use health::{Dead, Health, HealthPlugin, MaxHealth};
+pub use lives::{Lives, LivesPlugin};
+pub use physics::GameLayer;
+pub use pickup::PickupPlugin;
+pub use ship::{FlightModel, RotateMethod, Ship, ShipPlugin, ThrustFrame, Thrusters};
+pub use template::{Template, TemplateExt};
 pub use vfx::VfxPlugin;
+pub use weapon::{Fires, WeaponDef, WeaponDefs, WeaponPlugin};
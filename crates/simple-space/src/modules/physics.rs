@@ -8,4 +8,5 @@ pub enum GameLayer {
     Enemy,
     Block,
     Bullet,
+    Pickup,
 }
@@ -0,0 +1,106 @@
+use avian2d::prelude::*;
+use bevy::prelude::*;
+use bevy_prototype_lyon::prelude::*;
+
+use super::{
+    game_state::{GameState, InGame},
+    health::{Dead, Health, MaxHealth},
+    physics::GameLayer,
+    ship::Ship,
+    template::Template,
+};
+
+pub struct PickupPlugin;
+
+impl Plugin for PickupPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            Update,
+            (collect_pickups, despawn_expired_pickups).run_if(in_state(GameState::Playing)),
+        );
+    }
+}
+
+/// Dropped by dying entities, collected by flying the ship into it.
+#[derive(Component, Clone)]
+#[require(Transform, Visibility)]
+pub enum Pickup {
+    /// Restores health, up to [`MaxHealth`].
+    Heal(f32),
+}
+
+pub struct PickupProp {
+    pub pickup: Pickup,
+    pub position: Vec2,
+}
+
+#[derive(Component)]
+struct PickupTimeout(Timer);
+
+const PICKUP_SIZE: f32 = 20.;
+const PICKUP_TIMEOUT: f32 = 20.;
+
+impl Template for Pickup {
+    type Prop = PickupProp;
+    fn construct(mut cmd: EntityCommands<'_>, prop: Self::Prop) -> EntityCommands<'_> {
+        cmd.insert((
+            prop.pickup,
+            Name::new("Pickup"),
+            StateScoped(InGame),
+            PickupTimeout(Timer::from_seconds(PICKUP_TIMEOUT, TimerMode::Once)),
+            Transform::from_translation(prop.position.extend(0.)),
+            RigidBody::Static,
+            Collider::rectangle(PICKUP_SIZE, PICKUP_SIZE),
+            Sensor,
+            CollisionLayers::new(GameLayer::Pickup, [GameLayer::Player]),
+            CollidingEntities::default(),
+        ))
+        .with_children(|parent| {
+            parent.spawn((
+                ShapeBundle {
+                    path: GeometryBuilder::build_as(&shapes::Rectangle {
+                        extents: Vec2::splat(PICKUP_SIZE),
+                        origin: RectangleOrigin::Center,
+                        radii: None,
+                    }),
+                    transform: Transform::from_rotation(Quat::from_rotation_z(45f32.to_radians())),
+                    ..default()
+                },
+                Fill::color(Color::WHITE.with_alpha(0.)),
+                Stroke::new(Color::WHITE, 2.),
+            ));
+        });
+        cmd
+    }
+}
+
+fn collect_pickups(
+    mut cmd: Commands,
+    pickups: Query<(Entity, &Pickup, &CollidingEntities), Changed<CollidingEntities>>,
+    mut ship: Query<(&mut Health, &MaxHealth), (With<Ship>, Without<Dead>)>,
+) {
+    for (id, pickup, entities) in pickups.iter() {
+        let Some((mut health, max_health)) = entities
+            .iter()
+            .find_map(|entity| ship.get_mut(*entity).ok())
+        else {
+            continue;
+        };
+        match pickup {
+            Pickup::Heal(amount) => health.0 = (health.0 + amount).min(max_health.0),
+        }
+        cmd.entity(id).despawn_recursive();
+    }
+}
+
+fn despawn_expired_pickups(
+    mut cmd: Commands,
+    mut pickups: Query<(Entity, &mut PickupTimeout)>,
+    time: Res<Time>,
+) {
+    for (id, mut timeout) in pickups.iter_mut() {
+        if timeout.0.tick(time.delta()).just_finished() {
+            cmd.entity(id).despawn_recursive();
+        }
+    }
+}
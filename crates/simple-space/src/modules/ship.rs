@@ -1,32 +1,85 @@
 use avian2d::{math::Vector, prelude::*};
 use bevy::prelude::*;
 use bevy_prototype_lyon::prelude::*;
-
-use crate::CursorPosition;
+use playground_core::CursorPosition;
+use playground_ui::{
+    ActionInput, Binding, DebugWatch, InputUIFocused, KeyBindingsAppExt, Level, Toasts,
+    action_just_pressed, no_input_focused,
+};
 
 use super::{
-    bullet::{Bullet, BulletProp, BulletType},
-    health::{HPBarConfig, Health},
+    ability::{Boost, Dash, Dashing},
+    ammo::EnergyPool,
+    camera::CameraTarget,
+    damage::Shield,
+    death::{DeathEffect, OnDeath},
+    game_state::{GameState, InGame},
+    health::{Dead, HPBarConfig, Health},
+    lives::Lives,
     physics::GameLayer,
-    template::TemplateExt,
+    weapon::{ActiveBeam, Weapon, WeaponDefs},
 };
 
 pub struct ShipPlugin;
 
 impl Plugin for ShipPlugin {
     fn build(&self, app: &mut App) {
-        app.add_systems(Startup, setup).add_systems(
-            Update,
-            (
-                ship_strafe,
-                switch_rotate_method.run_if(switch_key_pressed),
-                look_at_cursor.run_if(resource_equals(RotateMethod::Cursor)),
-                rotate_with_keyboard.run_if(resource_equals(RotateMethod::Keyboard)),
-                fire_tick,
-                shoot_bullet.run_if(fire_button_pressed.and(can_fire)),
-                // despawn_bullets,
-            ),
-        );
+        app.register_type::<Thrusters>()
+            .register_action("move_up", [KeyCode::KeyW])
+            .register_action("move_down", [KeyCode::KeyS])
+            .register_action("move_left", [KeyCode::KeyA])
+            .register_action("move_right", [KeyCode::KeyD])
+            .register_action("rotate_left", [KeyCode::ArrowLeft])
+            .register_action("rotate_right", [KeyCode::ArrowRight])
+            .register_action("switch_rotation", [KeyCode::ShiftRight])
+            .register_action(
+                "fire",
+                [
+                    Binding::Key(KeyCode::KeyJ),
+                    Binding::Mouse(MouseButton::Left),
+                    Binding::Key(KeyCode::ArrowUp),
+                    Binding::Key(KeyCode::ArrowDown),
+                ],
+            )
+            .register_action("weapon_1", [KeyCode::Digit1])
+            .register_action("weapon_2", [KeyCode::Digit2])
+            .register_action("weapon_3", [KeyCode::Digit3])
+            .register_action("weapon_4", [KeyCode::Digit4])
+            .register_action("weapon_5", [KeyCode::Digit5])
+            .register_action("weapon_6", [KeyCode::Digit6])
+            .register_action("weapon_7", [KeyCode::Digit7])
+            .register_action("weapon_8", [KeyCode::Digit8])
+            .register_action("weapon_9", [KeyCode::Digit9])
+            .register_action("reload", [KeyCode::KeyR])
+            .register_action("switch_flight_model", [KeyCode::KeyF])
+            .register_action("flight_assist", [KeyCode::KeyV])
+            .register_action("dash", [KeyCode::Space])
+            .register_action("boost", [KeyCode::ShiftLeft])
+            .insert_resource(RotateMethod::Cursor)
+            .insert_resource(FlightModel::Arcade)
+            .add_systems(OnEnter(InGame), setup)
+            .add_systems(
+                Update,
+                (
+                    ship_strafe.run_if(resource_equals(FlightModel::Arcade).and(no_input_focused)),
+                    ship_thrust.run_if(is_inertial),
+                    switch_flight_model
+                        .run_if(action_just_pressed("switch_flight_model").and(no_input_focused)),
+                    toggle_flight_assist
+                        .run_if(action_just_pressed("flight_assist").and(no_input_focused)),
+                    watch_ship_speed.run_if(resource_exists::<DebugWatch>),
+                    switch_rotate_method
+                        .run_if(action_just_pressed("switch_rotation").and(no_input_focused)),
+                    look_at_cursor.run_if(resource_equals(RotateMethod::Cursor)),
+                    rotate_with_keyboard
+                        .run_if(resource_equals(RotateMethod::Keyboard).and(no_input_focused)),
+                    pull_trigger,
+                    use_abilities,
+                    switch_weapon.run_if(no_input_focused),
+                    reload_weapon.run_if(action_just_pressed("reload").and(no_input_focused)),
+                )
+                    .run_if(in_state(GameState::Playing).and(ship_alive)),
+            );
     }
 }
 
@@ -37,9 +90,12 @@ pub struct Ship;
 #[derive(Component)]
 pub struct Nozzle;
 
-fn setup(mut cmd: Commands) {
-    cmd.insert_resource(FireCooldown(Timer::from_seconds(0.1, TimerMode::Repeating)));
+/// Controls are disabled while the ship is dead or respawning.
+fn ship_alive(ship: Query<(), (With<Ship>, Without<Dead>)>) -> bool {
+    !ship.is_empty()
+}
 
+fn setup(mut cmd: Commands, weapons: Res<WeaponDefs>) {
     let point1_length = 100.;
     let point23_length = 50.;
 
@@ -58,76 +114,83 @@ fn setup(mut cmd: Commands) {
         closed: true,
     };
 
-    cmd.insert_resource(RotateMethod::Cursor);
-
-    cmd.spawn((
-        Ship,
-        Health(100.),
-        MaxSpeed(1000.),
-        RigidBody::Dynamic,
-        GravityScale(0.),
-        SweptCcd::default(),
-        LockedAxes::ROTATION_LOCKED,
-        Collider::triangle(point1, point2, point3),
-        CollisionLayers::new(
-            GameLayer::Player,
-            [GameLayer::Default, GameLayer::Block, GameLayer::Enemy],
-        ),
-        HPBarConfig { y_offset: 65. },
-    ))
-    .with_children(|parent| {
-        parent.spawn((
-            ShapeBundle {
-                path: GeometryBuilder::build_as(&shape),
-                ..default()
-            },
-            Fill::color(Color::WHITE.with_alpha(0.)),
-            Stroke::new(Color::WHITE, 3.),
-        ));
-        parent.spawn((Nozzle, Transform::from_xyz(100., 0., 0.)));
-    });
+    let ship = cmd
+        .spawn((
+            Ship,
+            Name::new("Ship"),
+            CameraTarget,
+            StateScoped(InGame),
+            Health(100.),
+            Shield::new(50.).with_regen(10., 3.),
+            EnergyPool::new(100.).with_regen(20.),
+            MaxSpeed(1000.),
+            Dash::new(3000., 1.5),
+            Boost::new(1.6),
+            Thrusters::default(),
+            ExternalForce::default(),
+            LinearDamping::default(),
+            RigidBody::Dynamic,
+            GravityScale(0.),
+            SweptCcd::default(),
+            LockedAxes::ROTATION_LOCKED,
+            Collider::triangle(point1, point2, point3),
+            CollisionLayers::new(
+                GameLayer::Player,
+                [
+                    GameLayer::Default,
+                    GameLayer::Block,
+                    GameLayer::Enemy,
+                    GameLayer::Pickup,
+                ],
+            ),
+            HPBarConfig { y_offset: 65. },
+            Lives(3),
+            OnDeath(vec![
+                DeathEffect::Explosion,
+                DeathEffect::Respawn {
+                    delay: 2.,
+                    position: Vec2::ZERO,
+                    invulnerability: 3.,
+                },
+            ]),
+        ))
+        .with_children(|parent| {
+            parent.spawn((
+                ShapeBundle {
+                    path: GeometryBuilder::build_as(&shape),
+                    ..default()
+                },
+                Fill::color(Color::WHITE.with_alpha(0.)),
+                Stroke::new(Color::WHITE, 3.),
+            ));
+            parent.spawn((Nozzle, Transform::from_xyz(100., 0., 0.)));
+        })
+        .id();
+
+    if let Some(def) = weapons.weapons.first() {
+        cmd.entity(ship)
+            .insert(Weapon::new(def.clone(), ship_bullet_layers()));
+    }
 }
 
 #[derive(Component)]
 pub struct MaxSpeed(pub f32);
 
 fn ship_strafe(
-    keyboard: Res<ButtonInput<KeyCode>>,
-    ship: Single<(&mut LinearVelocity, &MaxSpeed), With<Ship>>,
+    input: ActionInput,
+    ship: Single<(&mut LinearVelocity, &MaxSpeed, Option<&Boost>), (With<Ship>, Without<Dashing>)>,
     mut progress: Local<f32>,
     mut last_direction: Local<Vector>, // Track the previous input direction
     time: Res<Time>,
 ) {
-    let (mut linvel, max_speed) = ship.into_inner();
-    let mut input_direction = Vector::ZERO;
-
-    // Horizontal input
-    match (
-        keyboard.pressed(KeyCode::KeyD),
-        keyboard.pressed(KeyCode::KeyA),
-    ) {
-        (true, false) => input_direction.x = 1.0,
-        (false, true) => input_direction.x = -1.0,
-        _ => {}
-    }
-
-    // Vertical input
-    match (
-        keyboard.pressed(KeyCode::KeyW),
-        keyboard.pressed(KeyCode::KeyS),
-    ) {
-        (true, false) => input_direction.y = 1.0,
-        (false, true) => input_direction.y = -1.0,
-        _ => {}
-    }
+    let (mut linvel, max_speed, boost) = ship.into_inner();
+    let max_speed = max_speed.0 * boost.map_or(1., Boost::speed_multiplier);
+    let input_direction = movement_direction(&input);
 
     // Reset progress if the input direction changes significantly.
-    if input_direction != Vector::ZERO {
-        input_direction = input_direction.normalize();
-        if input_direction != *last_direction {
-            *progress = 0.0;
-            *last_direction = input_direction;
-        }
+    if input_direction != Vector::ZERO && input_direction != *last_direction {
+        *progress = 0.0;
+        *last_direction = input_direction;
     }
 
     // Ease out expo function
@@ -149,7 +212,7 @@ fn ship_strafe(
         let alpha = ease_out_expo(t_normalized);
 
         // Calculate target velocity based on max speed and input direction.
-        let target_velocity = input_direction * max_speed.0;
+        let target_velocity = input_direction * max_speed;
 
         // Smoothly interpolate from current velocity to target velocity.
         linvel.0 = linvel.0.lerp(target_velocity, alpha);
@@ -162,9 +225,185 @@ fn ship_strafe(
     }
 
     // Optionally, clamp velocity to max speed if overshooting.
-    if linvel.0.length() > max_speed.0 {
-        linvel.0 = linvel.0.normalize() * max_speed.0;
+    if linvel.0.length() > max_speed {
+        linvel.0 = linvel.0.normalize() * max_speed;
+    }
+}
+
+/// Normalized direction of the movement actions, zero without input.
+fn movement_direction(input: &ActionInput) -> Vector {
+    let mut direction = Vector::ZERO;
+
+    // Horizontal input
+    match (input.pressed("move_right"), input.pressed("move_left")) {
+        (true, false) => direction.x = 1.0,
+        (false, true) => direction.x = -1.0,
+        _ => {}
     }
+
+    // Vertical input
+    match (input.pressed("move_up"), input.pressed("move_down")) {
+        (true, false) => direction.y = 1.0,
+        (false, true) => direction.y = -1.0,
+        _ => {}
+    }
+
+    direction.normalize_or_zero()
+}
+
+/// How the movement actions move the ship.
+#[derive(Resource, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum FlightModel {
+    /// Velocity eases toward the input direction.
+    Arcade,
+    /// The input fires [`Thrusters`] and the ship keeps drifting.
+    Inertial(ThrustFrame),
+}
+
+/// What the movement actions are relative to in [`FlightModel::Inertial`].
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum ThrustFrame {
+    /// Up is up on the screen.
+    Screen,
+    /// Up is forward, left and right strafe.
+    Heading,
+}
+
+fn is_inertial(flight_model: Res<FlightModel>) -> bool {
+    matches!(*flight_model, FlightModel::Inertial(_))
+}
+
+/// Used by [`FlightModel::Inertial`].
+#[derive(Component, Reflect, Clone, Copy, Debug)]
+#[reflect(Component)]
+pub struct Thrusters {
+    /// Acceleration at full thrust, applied as a force so collisions still
+    /// push the ship around.
+    pub thrust: f32,
+    pub damping: f32,
+    /// Brakes when no movement action is held.
+    pub assist: bool,
+    /// Deceleration of the flight assist.
+    pub assist_brake: f32,
+}
+
+impl Default for Thrusters {
+    fn default() -> Self {
+        Self {
+            thrust: 1500.,
+            damping: 0.2,
+            assist: true,
+            assist_brake: 1000.,
+        }
+    }
+}
+
+fn ship_thrust(
+    input: ActionInput,
+    focused: Option<Res<InputUIFocused>>,
+    flight_model: Res<FlightModel>,
+    ship: Single<
+        (
+            &mut ExternalForce,
+            &mut LinearDamping,
+            &mut LinearVelocity,
+            &ComputedMass,
+            &Transform,
+            &Thrusters,
+            &MaxSpeed,
+            Option<&Boost>,
+        ),
+        (With<Ship>, Without<Dashing>),
+    >,
+    time: Res<Time>,
+) {
+    let (mut force, mut damping, mut linvel, mass, transform, thrusters, max_speed, boost) =
+        ship.into_inner();
+    let multiplier = boost.map_or(1., Boost::speed_multiplier);
+    let direction = if no_input_focused(focused) {
+        movement_in_world(&input, *flight_model, transform.rotation)
+    } else {
+        Vector::ZERO
+    };
+
+    let acceleration = if direction != Vector::ZERO {
+        direction * thrusters.thrust * multiplier
+    } else if thrusters.assist && linvel.0 != Vector::ZERO {
+        // Don't overshoot past zero within a frame
+        let brake = thrusters
+            .assist_brake
+            .min(linvel.length() / time.delta_secs().max(f32::EPSILON));
+        -linvel.normalize() * brake
+    } else {
+        Vector::ZERO
+    };
+    force.set_force(acceleration * mass.value());
+    if damping.0 != thrusters.damping {
+        damping.0 = thrusters.damping;
+    }
+
+    let max_speed = max_speed.0 * multiplier;
+    if linvel.0.length() > max_speed {
+        linvel.0 = linvel.0.normalize() * max_speed;
+    }
+}
+
+/// [`movement_direction`] relative to what the [`FlightModel`] thrusts along.
+fn movement_in_world(input: &ActionInput, flight_model: FlightModel, rotation: Quat) -> Vector {
+    let direction = movement_direction(input);
+    match flight_model {
+        FlightModel::Arcade | FlightModel::Inertial(ThrustFrame::Screen) => direction,
+        FlightModel::Inertial(ThrustFrame::Heading) => {
+            // Rotate so that up is the ship's nose, which points along +X
+            Rotation::from(rotation) * Vector::new(direction.y, -direction.x)
+        }
+    }
+}
+
+fn switch_flight_model(
+    mut flight_model: ResMut<FlightModel>,
+    ship: Option<Single<(&mut ExternalForce, &mut LinearDamping), With<Ship>>>,
+    toasts: Option<ResMut<Toasts>>,
+) {
+    *flight_model = match *flight_model {
+        FlightModel::Arcade => FlightModel::Inertial(ThrustFrame::Screen),
+        FlightModel::Inertial(ThrustFrame::Screen) => FlightModel::Inertial(ThrustFrame::Heading),
+        FlightModel::Inertial(ThrustFrame::Heading) => FlightModel::Arcade,
+    };
+    if *flight_model == FlightModel::Arcade {
+        // Arcade sets the velocity directly, leftover thrust would fight it
+        if let Some(ship) = ship {
+            let (mut force, mut damping) = ship.into_inner();
+            force.clear();
+            damping.0 = 0.;
+        }
+    }
+    if let Some(mut toasts) = toasts {
+        let name = match *flight_model {
+            FlightModel::Arcade => "arcade",
+            FlightModel::Inertial(ThrustFrame::Screen) => "inertial, screen relative",
+            FlightModel::Inertial(ThrustFrame::Heading) => "inertial, heading relative",
+        };
+        toasts.push(Level::Info, format!("Flight model: {}", name));
+    }
+}
+
+fn toggle_flight_assist(
+    mut thrusters: Single<&mut Thrusters, With<Ship>>,
+    toasts: Option<ResMut<Toasts>>,
+) {
+    thrusters.assist = !thrusters.assist;
+    if let Some(mut toasts) = toasts {
+        let state = if thrusters.assist { "on" } else { "off" };
+        toasts.push(Level::Info, format!("Flight assist: {}", state));
+    }
+}
+
+fn watch_ship_speed(
+    ship: Single<&LinearVelocity, With<Ship>>,
+    mut debug_watch: ResMut<DebugWatch>,
+) {
+    debug_watch.watch("ship_speed", ship.length());
 }
 
 #[derive(Resource, PartialEq, Eq)]
@@ -173,10 +412,6 @@ pub enum RotateMethod {
     Keyboard,
 }
 
-fn switch_key_pressed(keyboard: Res<ButtonInput<KeyCode>>) -> bool {
-    keyboard.just_pressed(KeyCode::ShiftRight)
-}
-
 fn switch_rotate_method(mut rotate_method: ResMut<RotateMethod>) {
     *rotate_method = match *rotate_method {
         RotateMethod::Cursor => RotateMethod::Keyboard,
@@ -186,52 +421,29 @@ fn switch_rotate_method(mut rotate_method: ResMut<RotateMethod>) {
 
 fn look_at_cursor(
     cursor_position: Res<CursorPosition>,
-    camera: Single<(&Camera, &GlobalTransform)>,
     mut ship: Single<(&mut Transform, &GlobalTransform), With<Ship>>,
 ) {
-    let cursor_position = cursor_position.0;
-    let ship_on_viewport = camera
-        .0
-        .world_to_viewport(camera.1, ship.1.translation())
-        .unwrap();
-    let angle =
-        (ship_on_viewport.y - cursor_position.y).atan2(cursor_position.x - ship_on_viewport.x);
+    let angle = (cursor_position.world - ship.1.translation().truncate()).to_angle();
     ship.0.rotation =
         Quat::from(Rotation::from(ship.0.rotation).nlerp(Rotation::radians(angle), 0.5));
 }
 
 fn rotate_with_keyboard(
-    keyboard: Res<ButtonInput<KeyCode>>,
+    input: ActionInput,
     mut ship: Single<(&mut Transform, &GlobalTransform), With<Ship>>,
 ) {
-    if keyboard.pressed(KeyCode::ArrowRight) {
+    if input.pressed("rotate_right") {
         ship.0.rotate_local_z(-0.1);
     }
-    if keyboard.pressed(KeyCode::ArrowLeft) {
+    if input.pressed("rotate_left") {
         ship.0.rotate_local_z(0.1);
     }
 }
 
-#[derive(Resource)]
-struct FireCooldown(Timer);
-
-fn fire_button_pressed(
-    keyboard: Res<ButtonInput<KeyCode>>,
-    mouse: Res<ButtonInput<MouseButton>>,
-) -> bool {
-    keyboard.pressed(KeyCode::KeyJ)
-        || mouse.pressed(MouseButton::Left)
-        || keyboard.pressed(KeyCode::ArrowUp)
-        || keyboard.pressed(KeyCode::ArrowDown)
-}
-
-fn fire_tick(mut fire_cooldown: ResMut<FireCooldown>, time: Res<Time>) {
-    fire_cooldown.0.tick(time.delta());
-}
-
-fn can_fire(fire_cooldown: Res<FireCooldown>) -> bool {
-    fire_cooldown.0.just_finished()
-}
+const WEAPON_ACTIONS: [&str; 9] = [
+    "weapon_1", "weapon_2", "weapon_3", "weapon_4", "weapon_5", "weapon_6", "weapon_7", "weapon_8",
+    "weapon_9",
+];
 
 fn ship_bullet_layers() -> CollisionLayers {
     CollisionLayers::new(
@@ -240,18 +452,59 @@ fn ship_bullet_layers() -> CollisionLayers {
     )
 }
 
-fn shoot_bullet(
+fn pull_trigger(
+    input: ActionInput,
+    focused: Option<Res<InputUIFocused>>,
+    mut weapon: Single<&mut Weapon, With<Ship>>,
+) {
+    weapon.trigger = input.pressed("fire") && no_input_focused(focused);
+}
+
+fn switch_weapon(
     mut cmd: Commands,
-    ship: Single<Entity, With<Ship>>,
-    mut transform: Query<&mut Transform>,
-    nozzle: Single<&GlobalTransform, With<Nozzle>>,
+    input: ActionInput,
+    weapons: Res<WeaponDefs>,
+    ship: Single<(Entity, &mut Weapon), With<Ship>>,
+    toasts: Option<ResMut<Toasts>>,
 ) {
-    let ship = transform.get_mut(*ship).unwrap();
-    let angle = ship.rotation.to_euler(EulerRot::XYZ).2;
-    cmd.template::<Bullet>(BulletProp {
-        rotation: angle,
-        position: nozzle.translation(),
-        bullet_type: BulletType::Standard,
-        layers: ship_bullet_layers(),
-    });
+    let Some(def) = WEAPON_ACTIONS
+        .iter()
+        .zip(&weapons.weapons)
+        .find(|(action, _)| input.just_pressed(action))
+        .map(|(_, def)| def)
+    else {
+        return;
+    };
+    let (id, mut weapon) = ship.into_inner();
+    if weapon.def.name == def.name {
+        return;
+    }
+    weapon.switch(def.clone());
+    cmd.entity(id).remove::<ActiveBeam>();
+    if let Some(mut toasts) = toasts {
+        toasts.push(Level::Info, format!("Weapon: {}", def.name));
+    }
+}
+
+fn use_abilities(
+    input: ActionInput,
+    focused: Option<Res<InputUIFocused>>,
+    flight_model: Res<FlightModel>,
+    ship: Single<(&mut Dash, &mut Boost, &Transform), With<Ship>>,
+) {
+    let (mut dash, mut boost, transform) = ship.into_inner();
+    let focused = !no_input_focused(focused);
+    boost.active = input.pressed("boost") && !focused;
+    if input.just_pressed("dash") && !focused {
+        let mut direction = movement_in_world(&input, *flight_model, transform.rotation);
+        if direction == Vector::ZERO {
+            // Forward without movement input
+            direction = (transform.rotation * Vec3::X).truncate();
+        }
+        dash.activate(direction);
+    }
+}
+
+fn reload_weapon(mut weapon: Single<&mut Weapon, With<Ship>>) {
+    weapon.reload();
 }
@@ -5,7 +5,7 @@ use bevy_rand::prelude::{Entropy, WyRand};
 use bevy_vector_shapes::prelude::*;
 use rand_core::RngCore;
 
-use super::template::Template;
+use super::{game_state::InGame, template::Template};
 
 pub struct VfxPlugin;
 
@@ -21,6 +21,8 @@ pub struct Vfx;
 
 pub enum VfxType {
     Explosion,
+    /// Small burst where a bullet hit something solid.
+    Impact,
 }
 
 pub struct VfxProp {
@@ -77,6 +79,7 @@ impl Template for Vfx {
             VfxType::Explosion => {
                 cmd.insert((
                     Vfx,
+                    StateScoped(InGame),
                     Transform::from_translation(prop.position),
                     Particles::new(5, &mut prop.rng),
                     VFXDuration(Timer::from_seconds(
@@ -85,6 +88,15 @@ impl Template for Vfx {
                     )),
                 ));
             }
+            VfxType::Impact => {
+                cmd.insert((
+                    Vfx,
+                    StateScoped(InGame),
+                    Transform::from_translation(prop.position).with_scale(Vec3::splat(0.5)),
+                    Particles::new(3, &mut prop.rng),
+                    VFXDuration(Timer::from_seconds(0.15, TimerMode::Once)),
+                ));
+            }
         }
         cmd
     }
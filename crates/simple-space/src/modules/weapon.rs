@@ -0,0 +1,254 @@
+use std::{collections::HashMap, fs};
+
+use avian2d::prelude::*;
+use bevy::prelude::*;
+use bevy_vector_shapes::prelude::*;
+use serde::Deserialize;
+
+use super::{
+    ammo::{Ammo, AmmoState, EnergyPool},
+    bullet::{Bullet, BulletProp, BulletType},
+    damage::{DamageEvent, DamageKind},
+    game_state::GameState,
+    health::{Dead, Health},
+    ship::Nozzle,
+    template::TemplateExt,
+};
+
+pub struct WeaponPlugin;
+
+impl Plugin for WeaponPlugin {
+    fn build(&self, app: &mut App) {
+        let weapons_filepath = concat!(env!("CARGO_MANIFEST_DIR"), "/weapons.toml");
+        let weapons = fs::read_to_string(weapons_filepath)
+            .map_err(|err| err.to_string())
+            .and_then(|weapons| {
+                toml::from_str::<WeaponDefs>(&weapons).map_err(|err| err.to_string())
+            })
+            .unwrap_or_else(|err| {
+                warn!("Failed to load {}: {}", weapons_filepath, err);
+                toml::from_str(DEFAULT_WEAPONS).unwrap()
+            });
+
+        app.insert_resource(weapons)
+            .add_systems(Update, fire_weapons.run_if(in_state(GameState::Playing)))
+            .add_systems(Update, draw_beams)
+            .add_systems(OnEnter(GameState::Paused), stop_beams);
+    }
+}
+
+const DEFAULT_WEAPONS: &str = include_str!("../../weapons.toml");
+
+/// Weapon definitions from `weapons.toml`.
+#[derive(Resource, Deserialize)]
+pub struct WeaponDefs {
+    #[serde(rename = "weapon")]
+    pub weapons: Vec<WeaponDef>,
+}
+
+#[derive(Clone, Debug, Deserialize)]
+pub struct WeaponDef {
+    pub name: String,
+    /// Shots per second, ignored by beams.
+    #[serde(default)]
+    pub fire_rate: f32,
+    /// Degrees the bullets of one shot are fanned across.
+    #[serde(default)]
+    pub spread: f32,
+    /// Bullets per shot.
+    #[serde(default = "one")]
+    pub count: u32,
+    #[serde(default)]
+    pub ammo: Ammo,
+    #[serde(flatten)]
+    pub fires: Fires,
+}
+
+fn one() -> u32 {
+    1
+}
+
+#[derive(Clone, Debug, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Fires {
+    Bullet(BulletType),
+    Beam(Beam),
+}
+
+/// Continuous ray that damages the first thing it hits.
+#[derive(Clone, Debug, Deserialize)]
+pub struct Beam {
+    pub damage_per_second: f32,
+    pub kind: DamageKind,
+    pub range: f32,
+    pub width: f32,
+}
+
+/// Fires from the entity's [`Nozzle`] child, or its center without one,
+/// while `trigger` is held.
+#[derive(Component)]
+pub struct Weapon {
+    pub def: WeaponDef,
+    /// Layers of the bullets and beam, the filters decide what they hit.
+    pub layers: CollisionLayers,
+    pub trigger: bool,
+    cooldown: f32,
+    ammo: AmmoState,
+    /// Ammo of the weapons switched away from, by name. It doesn't reload or
+    /// cool down in the meantime.
+    holstered: HashMap<String, AmmoState>,
+    /// The trigger is held but the ammo doesn't allow firing.
+    blocked: bool,
+}
+
+impl Weapon {
+    pub fn new(def: WeaponDef, layers: CollisionLayers) -> Self {
+        Self {
+            ammo: AmmoState::new(&def.ammo),
+            def,
+            layers,
+            trigger: false,
+            cooldown: 0.,
+            holstered: HashMap::new(),
+            blocked: false,
+        }
+    }
+
+    /// Switches to `def`, keeping the ammo state of both weapons.
+    pub fn switch(&mut self, def: WeaponDef) {
+        let ammo = self
+            .holstered
+            .remove(&def.name)
+            .unwrap_or_else(|| AmmoState::new(&def.ammo));
+        let previous = std::mem::replace(&mut self.ammo, ammo);
+        self.holstered.insert(self.def.name.clone(), previous);
+        self.def = def;
+        self.blocked = false;
+    }
+
+    pub fn reload(&mut self) {
+        self.ammo.reload(&self.def.ammo);
+    }
+
+    pub fn ammo(&self) -> &AmmoState {
+        &self.ammo
+    }
+
+    pub fn is_blocked(&self) -> bool {
+        self.blocked
+    }
+}
+
+/// Where a beam is drawn this frame.
+#[derive(Component)]
+pub struct ActiveBeam {
+    start: Vec2,
+    end: Vec2,
+    width: f32,
+}
+
+fn fire_weapons(
+    mut cmd: Commands,
+    mut weapons: Query<
+        (
+            Entity,
+            &mut Weapon,
+            &GlobalTransform,
+            Option<&Children>,
+            Option<&mut EnergyPool>,
+        ),
+        Without<Dead>,
+    >,
+    nozzles: Query<&GlobalTransform, With<Nozzle>>,
+    health: Query<(), With<Health>>,
+    spatial_query: SpatialQuery,
+    mut damage: EventWriter<DamageEvent>,
+    time: Res<Time>,
+) {
+    for (id, mut weapon, transform, children, energy) in weapons.iter_mut() {
+        let weapon = &mut *weapon;
+        weapon.cooldown = (weapon.cooldown - time.delta_secs()).max(0.);
+        weapon.ammo.tick(&weapon.def.ammo, time.delta_secs());
+        let muzzle = children
+            .and_then(|children| nozzles.iter_many(children).next())
+            .unwrap_or(transform);
+        let angle = muzzle.rotation().to_euler(EulerRot::XYZ).2;
+        let position = muzzle.translation();
+
+        match &weapon.def.fires {
+            Fires::Bullet(bullet_type) => {
+                weapon.blocked = weapon.trigger
+                    && !weapon
+                        .ammo
+                        .can_fire(&weapon.def.ammo, energy.as_deref(), 1.);
+                if !weapon.trigger || weapon.blocked || weapon.cooldown > 0. {
+                    continue;
+                }
+                weapon.ammo.spend(&weapon.def.ammo, energy, 1.);
+                let count = weapon.def.count.max(1);
+                let spread = weapon.def.spread.to_radians();
+                for i in 0..count {
+                    let offset = if count == 1 {
+                        0.
+                    } else {
+                        spread * (i as f32 / (count - 1) as f32 - 0.5)
+                    };
+                    cmd.template::<Bullet>(BulletProp {
+                        rotation: angle + offset,
+                        position,
+                        bullet_type: bullet_type.clone(),
+                        layers: weapon.layers,
+                    });
+                }
+                weapon.cooldown = 1. / weapon.def.fire_rate.max(0.01);
+            }
+            Fires::Beam(beam) => {
+                let amount = time.delta_secs();
+                weapon.blocked = weapon.trigger
+                    && !weapon
+                        .ammo
+                        .can_fire(&weapon.def.ammo, energy.as_deref(), amount);
+                if !weapon.trigger || weapon.blocked {
+                    cmd.entity(id).remove::<ActiveBeam>();
+                    continue;
+                }
+                weapon.ammo.spend(&weapon.def.ammo, energy, amount);
+                let start = position.truncate();
+                let direction = Dir2::from_xy(angle.cos(), angle.sin()).unwrap_or(Dir2::X);
+                let filter = SpatialQueryFilter::from_mask(weapon.layers.filters);
+                let hit = spatial_query.cast_ray(start, direction, beam.range, true, &filter);
+                let length = hit.map_or(beam.range, |hit| hit.distance);
+                if let Some(hit) = hit.filter(|hit| health.contains(hit.entity)) {
+                    damage.send(DamageEvent {
+                        target: hit.entity,
+                        source: Some(id),
+                        amount: beam.damage_per_second * time.delta_secs(),
+                        kind: beam.kind,
+                    });
+                }
+                cmd.entity(id).insert(ActiveBeam {
+                    start,
+                    end: start + direction * length,
+                    width: beam.width,
+                });
+            }
+        }
+    }
+}
+
+/// Beams are only refreshed while playing, don't leave them drawn over the
+/// pause menu.
+fn stop_beams(mut cmd: Commands, beams: Query<Entity, With<ActiveBeam>>) {
+    for id in beams.iter() {
+        cmd.entity(id).remove::<ActiveBeam>();
+    }
+}
+
+fn draw_beams(mut painter: ShapePainter, beams: Query<&ActiveBeam>) {
+    for beam in beams.iter() {
+        painter.transform = Transform::IDENTITY;
+        painter.thickness = beam.width;
+        painter.color = Color::WHITE;
+        painter.line(beam.start.extend(0.), beam.end.extend(0.));
+    }
+}
@@ -1,13 +1,44 @@
-use bevy::prelude::*;
-use playground_ui::{DebugLog, DebugPanelText, Panel, PanelTitle, PlaygroundUIPlugin, TextUI};
+use avian2d::prelude::*;
+use bevy::{input::common_conditions::input_just_pressed, prelude::*};
+use playground_core::CursorPosition;
+use playground_ui::{
+    ButtonShortcut, ConsolePlugin, DebugLog, DebugLogView, DebugWatch, DebugWatchView,
+    InspectedEntity, InspectorComponents, InspectorView, KeyBindingsAppExt, KeyBindingsView, Level,
+    Panel, PanelTitle, PlaygroundUIPlugin, Plot, TextButton, TextUI, Toasts,
+};
 
 pub struct UIPlugin;
 
 impl Plugin for UIPlugin {
     fn build(&self, app: &mut App) {
-        app.add_plugins(PlaygroundUIPlugin)
+        let key_bindings_filepath = concat!(env!("CARGO_MANIFEST_DIR"), "/key_bindings.toml");
+
+        app.add_plugins((PlaygroundUIPlugin, ConsolePlugin))
             .init_resource::<DebugLog>()
-            .add_systems(Startup, build_ui);
+            .init_resource::<DebugWatch>()
+            .insert_resource(Toasts::new().with_log_level(Level::Warn))
+            .load_key_bindings(key_bindings_filepath)
+            .insert_resource(InspectorComponents(
+                [
+                    "Health",
+                    "MaxHealth",
+                    "Shield",
+                    "Armor",
+                    "EnergyPool",
+                    "Thrusters",
+                    "Lives",
+                    "LinearVelocity",
+                    "Transform",
+                    "CollisionLayers",
+                ]
+                .map(String::from)
+                .to_vec(),
+            ))
+            .add_systems(Startup, build_ui)
+            .add_systems(
+                Update,
+                select_entity.run_if(input_just_pressed(MouseButton::Right)),
+            );
     }
 }
 
@@ -28,11 +59,45 @@ fn build_ui(mut cmd: Commands) {
             .spawn((Panel, PanelTitle::new("Panel")))
             .with_children(|parent| {
                 parent.spawn(TextUI::new("Test text"));
+                parent.spawn((
+                    TextButton::new("Spawn enemy (E)"),
+                    ButtonShortcut(KeyCode::KeyE),
+                    Name::new("spawn_enemy"),
+                ));
+                parent
+                    .spawn((Panel, PanelTitle::new("Watch")))
+                    .with_children(|parent| {
+                        parent.spawn(DebugWatchView);
+                        parent.spawn(Plot::new("ship_speed"));
+                    });
                 parent
                     .spawn((Panel, PanelTitle::new("Debug")))
                     .with_children(|parent| {
-                        parent.spawn((DebugPanelText, TextUI::new("")));
+                        parent.spawn(DebugLogView);
+                    });
+                parent
+                    .spawn((Panel, PanelTitle::new("Inspector")))
+                    .with_children(|parent| {
+                        parent.spawn(InspectorView);
+                    });
+                parent
+                    .spawn((Panel, PanelTitle::new("Controls")))
+                    .with_children(|parent| {
+                        parent.spawn(KeyBindingsView);
                     });
             });
     });
 }
+
+fn select_entity(
+    cursor_position: Res<CursorPosition>,
+    spatial_query: SpatialQuery,
+    mut inspected: ResMut<InspectedEntity>,
+) {
+    if let Some(entity) = spatial_query
+        .point_intersections(cursor_position.world, &SpatialQueryFilter::default())
+        .first()
+    {
+        inspected.0 = Some(*entity);
+    }
+}
@@ -0,0 +1,66 @@
+mod common;
+
+use avian2d::prelude::*;
+use bevy::prelude::*;
+use common::{run_for, test_app};
+use simple_space::modules::{
+    AbilityPlugin, Boost, DamageEvent, DamageKind, DamagePlugin, Dash, Health, Invulnerable,
+};
+
+fn app() -> App {
+    test_app((DamagePlugin, AbilityPlugin))
+}
+
+#[test]
+fn dash_bursts_and_ignores_damage() {
+    let mut app = app();
+    let entity = app
+        .world_mut()
+        .spawn((
+            Dash::new(3000., 1.),
+            LinearVelocity::default(),
+            Health(100.),
+        ))
+        .id();
+
+    let mut dash = app.world_mut().get_mut::<Dash>(entity).unwrap();
+    assert!(dash.activate(Vec2::X));
+    assert!(!dash.activate(Vec2::Y));
+    app.update();
+    assert_eq!(
+        app.world().get::<LinearVelocity>(entity).unwrap().0,
+        Vec2::X * 3000.
+    );
+    assert!(app.world().get::<Invulnerable>(entity).is_some());
+
+    app.world_mut().send_event(DamageEvent {
+        target: entity,
+        source: None,
+        amount: 50.,
+        kind: DamageKind::Kinetic,
+    });
+    app.update();
+    assert_eq!(app.world().get::<Health>(entity).unwrap().0, 100.);
+
+    run_for(&mut app, 1.);
+    assert!(app.world().get::<Invulnerable>(entity).is_none());
+    assert!(app.world().get::<Dash>(entity).unwrap().is_ready());
+}
+
+#[test]
+fn boost_runs_dry() {
+    let mut app = app();
+    let mut boost = Boost::new(2.);
+    boost.active = true;
+    let entity = app.world_mut().spawn(boost).id();
+
+    app.update();
+    assert_eq!(
+        app.world().get::<Boost>(entity).unwrap().speed_multiplier(),
+        2.
+    );
+    run_for(&mut app, 2.5);
+    let boost = app.world().get::<Boost>(entity).unwrap();
+    assert_eq!(boost.meter(), 0.);
+    assert_eq!(boost.speed_multiplier(), 1.);
+}
@@ -0,0 +1,59 @@
+use bevy::prelude::*;
+use simple_space::modules::{Ammo, AmmoState, EnergyPool};
+
+#[test]
+fn magazine_reloads_when_empty() {
+    let ammo = Ammo::Magazine {
+        size: 2,
+        reload: 1.,
+    };
+    let mut state = AmmoState::new(&ammo);
+
+    for _ in 0..2 {
+        assert!(state.can_fire(&ammo, None, 1.));
+        state.spend(&ammo, None, 1.);
+    }
+    assert!(state.is_reloading());
+    assert!(!state.can_fire(&ammo, None, 1.));
+
+    state.tick(&ammo, 0.5);
+    assert!(!state.can_fire(&ammo, None, 1.));
+    state.tick(&ammo, 0.5);
+    assert!(state.can_fire(&ammo, None, 1.));
+    assert_eq!(state.rounds(), 2);
+}
+
+#[test]
+fn overheated_weapon_waits_until_cool() {
+    let ammo = Ammo::Heat {
+        per_shot: 0.5,
+        cooling: 1.,
+    };
+    let mut state = AmmoState::new(&ammo);
+
+    state.spend(&ammo, None, 1.);
+    assert!(state.can_fire(&ammo, None, 1.));
+    state.spend(&ammo, None, 1.);
+    assert!(state.is_overheated());
+
+    state.tick(&ammo, 0.5);
+    assert!(!state.can_fire(&ammo, None, 1.));
+    state.tick(&ammo, 0.5);
+    assert!(state.can_fire(&ammo, None, 1.));
+}
+
+#[test]
+fn energy_is_shared_from_the_pool() {
+    let ammo = Ammo::Energy { per_shot: 40. };
+    let mut world = World::new();
+    let owner = world.spawn(EnergyPool::new(100.)).id();
+    let mut first = AmmoState::new(&ammo);
+    let mut second = AmmoState::new(&ammo);
+
+    first.spend(&ammo, world.get_mut::<EnergyPool>(owner), 1.);
+    second.spend(&ammo, world.get_mut::<EnergyPool>(owner), 1.);
+    let pool = world.get::<EnergyPool>(owner).unwrap();
+    assert_eq!(pool.current, 20.);
+    assert!(!first.can_fire(&ammo, Some(pool), 1.));
+    assert!(!first.can_fire(&ammo, None, 1.));
+}
@@ -0,0 +1,66 @@
+mod common;
+
+use avian2d::prelude::*;
+use bevy::prelude::*;
+use common::{FRAME, run_for, test_app};
+use playground_ui::DebugLog;
+use simple_space::modules::{
+    Bullet, BulletPlugin, BulletProp, BulletType, DamagePlugin, GameLayer, TemplateExt,
+};
+
+#[derive(Resource)]
+struct Firing(bool);
+
+fn app() -> App {
+    test_app((DamagePlugin, BulletPlugin, |app: &mut App| {
+        app.init_resource::<DebugLog>()
+            .insert_resource(Firing(true))
+            .add_systems(Update, fire.run_if(|firing: Res<Firing>| firing.0));
+    }))
+}
+
+fn fire(mut cmd: Commands) {
+    cmd.template::<Bullet>(BulletProp {
+        rotation: 0.,
+        position: Vec3::ZERO,
+        bullet_type: BulletType::default(),
+        layers: CollisionLayers::new(GameLayer::Bullet, [GameLayer::Block]),
+    });
+}
+
+fn bullets(app: &mut App) -> usize {
+    app.world_mut()
+        .query_filtered::<(), With<Bullet>>()
+        .iter(app.world())
+        .count()
+}
+
+#[test]
+fn sustained_fire_stays_bounded() {
+    let mut app = app();
+    let lifetime = BulletType::default().lifetime;
+    // One bullet per frame can't outlive its lifetime
+    let bound = (lifetime / FRAME.as_secs_f32()).ceil() as usize + 2;
+
+    for _ in 0..5 {
+        run_for(&mut app, lifetime);
+        let count = bullets(&mut app);
+        assert!(count > 0);
+        assert!(
+            count <= bound,
+            "{} bullets alive, expected at most {}",
+            count,
+            bound
+        );
+    }
+}
+
+#[test]
+fn bullets_are_gone_after_ceasing_fire() {
+    let mut app = app();
+    run_for(&mut app, 1.);
+    app.world_mut().resource_mut::<Firing>().0 = false;
+
+    run_for(&mut app, BulletType::default().lifetime + 0.1);
+    assert_eq!(bullets(&mut app), 0);
+}
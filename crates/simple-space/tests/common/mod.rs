@@ -0,0 +1,35 @@
+use std::time::Duration;
+
+use avian2d::prelude::*;
+use bevy::{prelude::*, state::app::StatesPlugin, time::TimeUpdateStrategy};
+use bevy_rand::{plugin::EntropyPlugin, prelude::WyRand};
+use simple_space::modules::GameState;
+
+pub const FRAME: Duration = Duration::from_nanos(16_666_667);
+
+/// Headless app with physics, advancing one [`FRAME`] per update while
+/// [`GameState::Playing`].
+pub fn test_app<M>(plugins: impl Plugins<M>) -> App {
+    let mut app = App::new();
+    app.add_plugins((
+        MinimalPlugins,
+        StatesPlugin,
+        TransformPlugin,
+        HierarchyPlugin,
+        AssetPlugin::default(),
+        PhysicsPlugins::default().with_length_unit(20.),
+        EntropyPlugin::<WyRand>::default(),
+    ))
+    .add_plugins(plugins)
+    .init_asset::<Mesh>()
+    .insert_state(GameState::Playing)
+    .insert_resource(TimeUpdateStrategy::ManualDuration(FRAME));
+    app.update();
+    app
+}
+
+pub fn run_for(app: &mut App, seconds: f32) {
+    for _ in 0..(seconds / FRAME.as_secs_f32()).round() as usize {
+        app.update();
+    }
+}
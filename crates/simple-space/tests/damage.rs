@@ -0,0 +1,130 @@
+mod common;
+
+use bevy::prelude::*;
+use common::{run_for, test_app};
+use simple_space::modules::{
+    Armor, DamageEvent, DamageKind, DamagePlugin, Health, Resistances, Shield,
+};
+
+fn hit(app: &mut App, target: Entity, amount: f32, kind: DamageKind) {
+    app.world_mut().send_event(DamageEvent {
+        target,
+        source: None,
+        amount,
+        kind,
+    });
+    app.update();
+}
+
+fn health(app: &App, entity: Entity) -> f32 {
+    app.world().get::<Health>(entity).unwrap().0
+}
+
+fn shield(app: &App, entity: Entity) -> f32 {
+    app.world().get::<Shield>(entity).unwrap().current
+}
+
+#[test]
+fn resistance_armor_and_shield_reduce_in_order() {
+    let mut app = test_app(DamagePlugin);
+    let entity = app
+        .world_mut()
+        .spawn((
+            Resistances {
+                kinetic: 0.5,
+                ..default()
+            },
+            Armor(10.),
+            Shield::new(20.),
+            Health(100.),
+        ))
+        .id();
+
+    // 100 halved, minus 10 armor, 20 absorbed by the shield
+    hit(&mut app, entity, 100., DamageKind::Kinetic);
+    assert_eq!(shield(&app, entity), 0.);
+    assert_eq!(health(&app, entity), 80.);
+}
+
+#[test]
+fn energy_ignores_armor() {
+    let mut app = test_app(DamagePlugin);
+    let entity = app
+        .world_mut()
+        .spawn((
+            Resistances {
+                energy: 0.5,
+                ..default()
+            },
+            Armor(10.),
+            Health(100.),
+        ))
+        .id();
+
+    hit(&mut app, entity, 40., DamageKind::Energy);
+    assert_eq!(health(&app, entity), 80.);
+    hit(&mut app, entity, 40., DamageKind::Kinetic);
+    assert_eq!(health(&app, entity), 50.);
+}
+
+#[test]
+fn armor_soaks_small_hits_before_the_shield() {
+    let mut app = test_app(DamagePlugin);
+    let entity = app
+        .world_mut()
+        .spawn((Armor(10.), Shield::new(20.), Health(100.)))
+        .id();
+
+    hit(&mut app, entity, 5., DamageKind::Explosive);
+    assert_eq!(shield(&app, entity), 20.);
+    assert_eq!(health(&app, entity), 100.);
+}
+
+#[test]
+fn observers_change_damage_before_reductions() {
+    let mut app = test_app(DamagePlugin);
+    let entity = app
+        .world_mut()
+        .spawn((Armor(10.), Health(100.)))
+        .observe(|mut trigger: Trigger<DamageEvent>| {
+            trigger.event_mut().amount *= 2.;
+        })
+        .id();
+
+    hit(&mut app, entity, 30., DamageKind::Kinetic);
+    assert_eq!(health(&app, entity), 50.);
+}
+
+#[test]
+fn cancelled_damage_is_not_applied() {
+    let mut app = test_app(DamagePlugin);
+    let entity = app
+        .world_mut()
+        .spawn((Shield::new(20.), Health(100.)))
+        .observe(|mut trigger: Trigger<DamageEvent>| {
+            trigger.event_mut().cancel();
+        })
+        .id();
+
+    hit(&mut app, entity, 50., DamageKind::Kinetic);
+    assert_eq!(shield(&app, entity), 20.);
+    assert_eq!(health(&app, entity), 100.);
+}
+
+#[test]
+fn shield_recharges_after_delay() {
+    let mut app = test_app(DamagePlugin);
+    let entity = app
+        .world_mut()
+        .spawn((Shield::new(50.).with_regen(100., 1.), Health(100.)))
+        .id();
+
+    hit(&mut app, entity, 30., DamageKind::Kinetic);
+    assert_eq!(shield(&app, entity), 20.);
+    assert_eq!(health(&app, entity), 100.);
+
+    run_for(&mut app, 0.5);
+    assert_eq!(shield(&app, entity), 20.);
+    run_for(&mut app, 1.);
+    assert_eq!(shield(&app, entity), 50.);
+}
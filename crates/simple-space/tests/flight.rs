@@ -0,0 +1,157 @@
+mod common;
+
+use std::f32::consts::PI;
+
+use avian2d::prelude::*;
+use bevy::prelude::*;
+use common::{run_for, test_app};
+use playground_core::CursorPosition;
+use simple_space::modules::{
+    AbilityPlugin, Dashing, FlightModel, GameStatePlugin, RotateMethod, Ship, ShipPlugin,
+    ThrustFrame, Thrusters, WeaponDefs,
+};
+
+fn app(flight_model: FlightModel) -> App {
+    test_app((
+        GameStatePlugin,
+        ShipPlugin,
+        AbilityPlugin,
+        move |app: &mut App| {
+            app.init_resource::<ButtonInput<KeyCode>>()
+                .init_resource::<ButtonInput<MouseButton>>()
+                .init_resource::<CursorPosition>()
+                .insert_resource(WeaponDefs { weapons: vec![] })
+                // Keep the heading where the test puts it
+                .insert_resource(RotateMethod::Keyboard)
+                .insert_resource(flight_model);
+        },
+    ))
+}
+
+fn ship(app: &mut App) -> Entity {
+    app.world_mut()
+        .query_filtered::<Entity, With<Ship>>()
+        .single(app.world())
+}
+
+fn hold(app: &mut App, key: KeyCode) {
+    app.world_mut()
+        .resource_mut::<ButtonInput<KeyCode>>()
+        .press(key);
+}
+
+fn release(app: &mut App, key: KeyCode) {
+    let mut keys = app.world_mut().resource_mut::<ButtonInput<KeyCode>>();
+    keys.release(key);
+    keys.clear();
+}
+
+/// Presses `key` for a single update.
+fn tap(app: &mut App, key: KeyCode) {
+    hold(app, key);
+    app.update();
+    release(app, key);
+}
+
+fn velocity(app: &mut App) -> Vec2 {
+    let ship = ship(app);
+    app.world().get::<LinearVelocity>(ship).unwrap().0
+}
+
+#[test]
+fn thrust_follows_the_thrust_frame() {
+    // The nose points left, up is forward only relative to the heading
+    for (frame, expected) in [
+        (ThrustFrame::Screen, Vec2::Y),
+        (ThrustFrame::Heading, Vec2::NEG_X),
+    ] {
+        let mut app = app(FlightModel::Inertial(frame));
+        let ship = ship(&mut app);
+        app.world_mut().get_mut::<Transform>(ship).unwrap().rotation = Quat::from_rotation_z(PI);
+
+        hold(&mut app, KeyCode::KeyW);
+        run_for(&mut app, 0.5);
+        let velocity = velocity(&mut app);
+        assert!(velocity.length() > 100., "{:?}: {}", frame, velocity);
+        assert!(
+            velocity.normalize().dot(expected) > 0.99,
+            "{:?}: {}",
+            frame,
+            velocity
+        );
+    }
+}
+
+#[test]
+fn flight_assist_brakes_to_rest() {
+    let mut app = app(FlightModel::Inertial(ThrustFrame::Screen));
+    let ship = ship(&mut app);
+
+    app.world_mut().get_mut::<LinearVelocity>(ship).unwrap().0 = Vec2::new(400., 0.);
+    run_for(&mut app, 1.);
+    assert!(velocity(&mut app).length() < 1.);
+
+    // Without the assist only the damping slows the ship down
+    app.world_mut().get_mut::<Thrusters>(ship).unwrap().assist = false;
+    app.world_mut().get_mut::<LinearVelocity>(ship).unwrap().0 = Vec2::new(400., 0.);
+    run_for(&mut app, 1.);
+    assert!(velocity(&mut app).length() > 250.);
+}
+
+#[test]
+fn flight_model_and_assist_toggle_at_runtime() {
+    let mut app = app(FlightModel::Arcade);
+    let ship = ship(&mut app);
+
+    tap(&mut app, KeyCode::KeyF);
+    assert_eq!(
+        *app.world().resource::<FlightModel>(),
+        FlightModel::Inertial(ThrustFrame::Screen)
+    );
+    tap(&mut app, KeyCode::KeyF);
+    assert_eq!(
+        *app.world().resource::<FlightModel>(),
+        FlightModel::Inertial(ThrustFrame::Heading)
+    );
+
+    hold(&mut app, KeyCode::KeyW);
+    run_for(&mut app, 0.2);
+    release(&mut app, KeyCode::KeyW);
+    assert_ne!(
+        app.world().get::<ExternalForce>(ship).unwrap().force(),
+        Vec2::ZERO
+    );
+    // Back to arcade, leftover thrust is dropped
+    tap(&mut app, KeyCode::KeyF);
+    assert_eq!(*app.world().resource::<FlightModel>(), FlightModel::Arcade);
+    assert_eq!(
+        app.world().get::<ExternalForce>(ship).unwrap().force(),
+        Vec2::ZERO
+    );
+
+    tap(&mut app, KeyCode::KeyV);
+    assert!(!app.world().get::<Thrusters>(ship).unwrap().assist);
+    tap(&mut app, KeyCode::KeyV);
+    assert!(app.world().get::<Thrusters>(ship).unwrap().assist);
+}
+
+#[test]
+fn dash_cuts_thrust() {
+    let mut app = app(FlightModel::Inertial(ThrustFrame::Screen));
+    let ship = ship(&mut app);
+
+    hold(&mut app, KeyCode::KeyW);
+    run_for(&mut app, 0.2);
+    assert_ne!(
+        app.world().get::<ExternalForce>(ship).unwrap().force(),
+        Vec2::ZERO
+    );
+
+    tap(&mut app, KeyCode::Space);
+    app.update();
+    assert!(app.world().get::<Dashing>(ship).is_some());
+    assert_eq!(
+        app.world().get::<ExternalForce>(ship).unwrap().force(),
+        Vec2::ZERO
+    );
+}
@@ -0,0 +1,54 @@
+mod common;
+
+use avian2d::prelude::*;
+use bevy::prelude::*;
+use common::{run_for, test_app};
+use simple_space::modules::{
+    Dead, DeathEffect, DeathPlugin, Health, HealthPlugin, Invulnerable, Lives, MaxHealth, OnDeath,
+};
+
+fn app() -> App {
+    test_app((HealthPlugin, DeathPlugin))
+}
+
+fn kill(app: &mut App, entity: Entity) {
+    app.world_mut().get_mut::<Health>(entity).unwrap().0 = 0.;
+    app.update();
+}
+
+#[test]
+fn respawns_until_out_of_lives() {
+    let mut app = app();
+    let entity = app
+        .world_mut()
+        .spawn((
+            Health(10.),
+            MaxHealth(10.),
+            Lives(2),
+            OnDeath(vec![DeathEffect::Respawn {
+                delay: 0.5,
+                position: Vec2::ZERO,
+                invulnerability: 1.,
+            }]),
+            RigidBody::Dynamic,
+            Collider::circle(10.),
+            Transform::from_xyz(500., 0., 0.),
+        ))
+        .id();
+    app.update();
+
+    kill(&mut app, entity);
+    assert!(app.world().get::<Dead>(entity).is_some());
+    assert_eq!(app.world().get::<Lives>(entity).unwrap().0, 1);
+
+    run_for(&mut app, 0.7);
+    let world = app.world();
+    assert!(world.get::<Dead>(entity).is_none());
+    assert_eq!(world.get::<Health>(entity).unwrap().0, 10.);
+    assert_eq!(world.get::<Position>(entity).unwrap().0, Vec2::ZERO);
+    assert!(world.get::<Invulnerable>(entity).is_some());
+
+    kill(&mut app, entity);
+    app.update();
+    assert!(app.world().get_entity(entity).is_err());
+}
@@ -0,0 +1,38 @@
+use simple_space::modules::{Ammo, Fires, WeaponDefs};
+
+#[test]
+fn weapons_file_parses() {
+    let defs = toml::from_str::<WeaponDefs>(include_str!("../weapons.toml")).unwrap();
+    let names = defs
+        .weapons
+        .iter()
+        .map(|weapon| weapon.name.as_str())
+        .collect::<Vec<_>>();
+    assert_eq!(
+        names,
+        [
+            "Blaster",
+            "Spread shot",
+            "Rail",
+            "Heavy cannon",
+            "Homing missile",
+            "Laser"
+        ]
+    );
+
+    let spread = &defs.weapons[1];
+    assert_eq!(spread.count, 5);
+    let Fires::Bullet(rail) = &defs.weapons[2].fires else {
+        panic!("rail should fire bullets");
+    };
+    assert_eq!(rail.pierce, 5);
+    let Fires::Bullet(missile) = &defs.weapons[4].fires else {
+        panic!("missile should fire bullets");
+    };
+    assert!(missile.homing.is_some());
+    assert!(matches!(defs.weapons[5].fires, Fires::Beam(_)));
+
+    assert!(matches!(defs.weapons[0].ammo, Ammo::Heat { .. }));
+    assert!(matches!(spread.ammo, Ammo::Magazine { size: 8, .. }));
+    assert!(matches!(defs.weapons[5].ammo, Ammo::Energy { .. }));
+}
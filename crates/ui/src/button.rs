@@ -0,0 +1,113 @@
+use bevy::prelude::*;
+
+use crate::{TextUI, Theme, no_input_focused};
+
+pub struct ButtonPlugin;
+
+impl Plugin for ButtonPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            Update,
+            (
+                update_button_style,
+                button_shortcut.run_if(no_input_focused),
+            ),
+        )
+        .add_observer(create_button);
+    }
+}
+
+/// Clickable button with a text label. Triggers [`ButtonPressed`] on itself
+/// when clicked or when its [`ButtonShortcut`] is pressed.
+#[derive(Component)]
+#[require(Node, Interaction)]
+pub struct TextButton(String);
+
+impl TextButton {
+    pub fn new(label: impl Into<String>) -> Self {
+        Self(label.into())
+    }
+}
+
+/// Key that presses the button while no input is focused.
+#[derive(Component)]
+pub struct ButtonShortcut(pub KeyCode);
+
+/// Greys out the widget and ignores presses.
+#[derive(Component)]
+pub struct Disabled;
+
+#[derive(Event)]
+pub struct ButtonPressed;
+
+#[derive(Component)]
+struct ButtonLabel;
+
+fn create_button(
+    trigger: Trigger<OnAdd, TextButton>,
+    mut cmd: Commands,
+    button: Query<&TextButton>,
+    theme: Res<Theme>,
+) {
+    let TextButton(label) = button.get(trigger.entity()).unwrap();
+    cmd.entity(trigger.entity())
+        .insert((
+            Node {
+                justify_content: JustifyContent::Center,
+                border: UiRect::all(Val::Px(1.)),
+                padding: UiRect::axes(Val::Px(6.), Val::Px(2.)),
+                ..default()
+            },
+            BorderColor(theme.border),
+            BackgroundColor(theme.button),
+        ))
+        .with_children(|parent| {
+            parent.spawn((ButtonLabel, TextUI::new(label.clone())));
+        })
+        .observe(press_button);
+}
+
+fn press_button(
+    trigger: Trigger<Pointer<Click>>,
+    mut cmd: Commands,
+    button: Query<(), (With<TextButton>, Without<Disabled>)>,
+) {
+    if trigger.button == PointerButton::Primary && button.contains(trigger.entity()) {
+        cmd.trigger_targets(ButtonPressed, trigger.entity());
+    }
+}
+
+fn button_shortcut(
+    mut cmd: Commands,
+    key_input: Res<ButtonInput<KeyCode>>,
+    button: Query<(Entity, &ButtonShortcut), (With<TextButton>, Without<Disabled>)>,
+) {
+    for (entity, ButtonShortcut(key)) in button.iter() {
+        if key_input.just_pressed(*key) {
+            cmd.trigger_targets(ButtonPressed, entity);
+        }
+    }
+}
+
+fn update_button_style(
+    theme: Res<Theme>,
+    mut button: Query<
+        (&Interaction, Has<Disabled>, &mut BackgroundColor, &Children),
+        With<TextButton>,
+    >,
+    mut label: Query<&mut TextColor, With<ButtonLabel>>,
+) {
+    for (interaction, disabled, mut background_color, children) in button.iter_mut() {
+        let (background, text) = match (disabled, interaction) {
+            (true, _) => (theme.button_disabled, theme.text_disabled),
+            (false, Interaction::Pressed) => (theme.button_pressed, theme.text),
+            (false, Interaction::Hovered) => (theme.button_hovered, theme.text),
+            (false, Interaction::None) => (theme.button, theme.text),
+        };
+        background_color.set_if_neq(BackgroundColor(background));
+        let mut labels = label.iter_many_mut(children);
+        while let Some(mut color) = labels.fetch_next() {
+            color.set_if_neq(TextColor(text));
+        }
+    }
+}
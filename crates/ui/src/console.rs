@@ -0,0 +1,489 @@
+use std::{collections::BTreeMap, fmt::Display, str::FromStr};
+
+use bevy::{ecs::system::SystemId, prelude::*, reflect::TypeRegistry};
+use bevy_simple_text_input::{TextInputInactive, TextInputSystem, TextInputValue};
+
+use crate::{
+    DebugLog, DebugLogView, InputUI, InputUIFocused, InputUISubmitEvent, InputUInitialValue,
+    KeepFocusOnSubmit, Level, MaxHeight, MaxWidth, NoUndo, TextUI, Theme,
+    reflect_value::{format_reflect, set_from_str},
+};
+
+/// Drop-down console toggled with backtick. Output goes to [`DebugLog`].
+pub struct ConsolePlugin;
+
+impl Plugin for ConsolePlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<DebugLog>()
+            .init_resource::<ConsoleCommands>()
+            .init_resource::<ConsoleHistory>()
+            .register_console_command("help", "list commands", help)
+            .register_console_command("clear", "clear the log", clear)
+            .register_console_command("quit", "exit the app", quit)
+            .register_console_command("timescale", "timescale [speed]", timescale)
+            .register_console_command("get", "get <Resource>[.path]", get_resource)
+            .register_console_command("set", "set <Resource>.path <value>", set_resource)
+            .add_systems(Startup, spawn_console)
+            .add_systems(
+                Update,
+                (
+                    toggle_console,
+                    (console_history, console_completion).run_if(console_focused),
+                )
+                    .chain()
+                    .after(TextInputSystem),
+            );
+    }
+}
+
+pub type ConsoleResult = Result<String, String>;
+
+pub trait ConsoleAppExt {
+    /// Registers a command that gets the line arguments as input, the
+    /// returned text is written to the log.
+    fn register_console_command<M>(
+        &mut self,
+        name: impl Into<String>,
+        help: impl Into<String>,
+        system: impl IntoSystem<In<ConsoleArgs>, ConsoleResult, M> + 'static,
+    ) -> &mut Self;
+}
+
+impl ConsoleAppExt for App {
+    fn register_console_command<M>(
+        &mut self,
+        name: impl Into<String>,
+        help: impl Into<String>,
+        system: impl IntoSystem<In<ConsoleArgs>, ConsoleResult, M> + 'static,
+    ) -> &mut Self {
+        let world = self.world_mut();
+        let system = world.register_system(system);
+        world
+            .get_resource_or_init::<ConsoleCommands>()
+            .0
+            .insert(name.into(), ConsoleCommand {
+                help: help.into(),
+                system,
+            });
+        self
+    }
+}
+
+struct ConsoleCommand {
+    help: String,
+    system: SystemId<In<ConsoleArgs>, ConsoleResult>,
+}
+
+#[derive(Resource, Default)]
+pub struct ConsoleCommands(BTreeMap<String, ConsoleCommand>);
+
+impl ConsoleCommands {
+    pub fn names(&self) -> impl Iterator<Item = &str> {
+        self.0.keys().map(|name| name.as_str())
+    }
+}
+
+/// Arguments after the command name, quoted arguments can contain spaces.
+#[derive(Debug, Clone, Default)]
+pub struct ConsoleArgs(Vec<String>);
+
+impl ConsoleArgs {
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    pub fn raw(&self, index: usize) -> Option<&str> {
+        self.0.get(index).map(|arg| arg.as_str())
+    }
+
+    pub fn get<T: FromStr>(&self, index: usize) -> Result<T, String>
+    where
+        T::Err: Display,
+    {
+        let arg = self
+            .raw(index)
+            .ok_or_else(|| format!("missing argument {}", index + 1))?;
+        arg.parse()
+            .map_err(|err| format!("invalid argument {} '{}': {}", index + 1, arg, err))
+    }
+
+    pub fn get_or<T: FromStr>(&self, index: usize, default: T) -> Result<T, String>
+    where
+        T::Err: Display,
+    {
+        match self.raw(index) {
+            Some(_) => self.get(index),
+            None => Ok(default),
+        }
+    }
+
+    /// Remaining arguments joined back with spaces.
+    pub fn rest(&self, index: usize) -> String {
+        self.0.get(index..).unwrap_or_default().join(" ")
+    }
+}
+
+fn tokenize(line: &str) -> Vec<String> {
+    let mut tokens = vec![];
+    let mut token = String::new();
+    let mut quoted = false;
+    let mut started = false;
+    for c in line.chars() {
+        match c {
+            '"' => {
+                quoted = !quoted;
+                started = true;
+            }
+            c if c.is_whitespace() && !quoted => {
+                if started {
+                    tokens.push(std::mem::take(&mut token));
+                    started = false;
+                }
+            }
+            c => {
+                token.push(c);
+                started = true;
+            }
+        }
+    }
+    if started {
+        tokens.push(token);
+    }
+    tokens
+}
+
+fn run_console_line(world: &mut World, line: &str) {
+    console_log(world, Level::Info, format!("> {}", line));
+    let mut tokens = tokenize(line);
+    if tokens.is_empty() {
+        return;
+    }
+    let name = tokens.remove(0);
+    let Some(system) = world
+        .resource::<ConsoleCommands>()
+        .0
+        .get(&name)
+        .map(|command| command.system)
+    else {
+        console_log(world, Level::Error, format!("unknown command '{}'", name));
+        return;
+    };
+    match world.run_system_with_input(system, ConsoleArgs(tokens)) {
+        Ok(Ok(output)) if output.is_empty() => {}
+        Ok(Ok(output)) => console_log(world, Level::Info, output),
+        Ok(Err(err)) => console_log(world, Level::Error, format!("{}: {}", name, err)),
+        Err(err) => console_log(world, Level::Error, format!("{}: {}", name, err)),
+    }
+}
+
+fn console_log(world: &mut World, level: Level, message: impl Into<String>) {
+    world
+        .resource_mut::<DebugLog>()
+        .log(level, "console", message);
+}
+
+#[derive(Component)]
+struct ConsoleRoot;
+
+#[derive(Component)]
+struct ConsoleInput;
+
+#[derive(Resource, Default)]
+struct ConsoleHistory {
+    lines: Vec<String>,
+    cursor: Option<usize>,
+}
+
+fn spawn_console(mut cmd: Commands) {
+    cmd.spawn((
+        ConsoleRoot,
+        Node {
+            display: Display::None,
+            position_type: PositionType::Absolute,
+            top: Val::Px(0.),
+            left: Val::Px(0.),
+            width: Val::Percent(100.),
+            flex_direction: FlexDirection::Column,
+            padding: UiRect::all(Val::Px(5.)),
+            row_gap: Val::Px(3.),
+            ..default()
+        },
+        BackgroundColor(Color::srgba(0.1, 0.1, 0.1, 0.95)),
+        GlobalZIndex(i32::MAX - 1),
+    ))
+    .with_children(|parent| {
+        parent.spawn((DebugLogView, MaxHeight(Val::Vh(35.))));
+        parent
+            .spawn(Node {
+                flex_direction: FlexDirection::Row,
+                ..default()
+            })
+            .with_children(|parent| {
+                parent.spawn(TextUI::new(">"));
+                parent
+                    .spawn((
+                        InputUI,
+                        ConsoleInput,
+                        KeepFocusOnSubmit,
+                        NoUndo,
+                        Name::new("console:input"),
+                        InputUInitialValue::default(),
+                        MaxWidth(Val::Percent(100.)),
+                    ))
+                    .observe(submit_console_line);
+            });
+    });
+}
+
+fn console_focused(focused: Res<InputUIFocused>, input: Query<(), With<ConsoleInput>>) -> bool {
+    focused.0.is_some_and(|entity| input.contains(entity))
+}
+
+fn toggle_console(
+    key_input: Res<ButtonInput<KeyCode>>,
+    mut root: Query<&mut Node, With<ConsoleRoot>>,
+    mut input: Query<
+        (
+            Entity,
+            &mut TextInputValue,
+            &mut TextInputInactive,
+            &mut BackgroundColor,
+        ),
+        With<ConsoleInput>,
+    >,
+    mut focused: ResMut<InputUIFocused>,
+    theme: Res<Theme>,
+) {
+    let Ok((entity, mut value, mut inactive, mut background_color)) = input.get_single_mut() else {
+        return;
+    };
+    // The text input has already seen the key press when the console was open
+    if value.0.contains('`') {
+        value.0 = value.0.replace('`', "");
+    }
+    let open = root.iter().any(|node| node.display != Display::None);
+    // Typing a backtick into another field shouldn't steal its focus
+    if !open && focused.0.is_some_and(|focused| focused != entity) {
+        return;
+    }
+    let close = open && key_input.just_pressed(KeyCode::Escape);
+    if !key_input.just_pressed(KeyCode::Backquote) && !close {
+        return;
+    }
+    for mut node in root.iter_mut() {
+        node.display = if open { Display::None } else { Display::Flex };
+    }
+    inactive.0 = open;
+    if open {
+        *background_color = BackgroundColor(theme.input_background);
+        if focused.0 == Some(entity) {
+            focused.0 = None;
+        }
+    } else {
+        *background_color = BackgroundColor(theme.input_focused);
+        focused.0 = Some(entity);
+    }
+}
+
+fn submit_console_line(
+    trigger: Trigger<InputUISubmitEvent>,
+    mut cmd: Commands,
+    mut input: Query<&mut TextInputValue>,
+    mut history: ResMut<ConsoleHistory>,
+) {
+    let mut value = input.get_mut(trigger.entity()).unwrap();
+    let line = value.0.trim().to_string();
+    value.0.clear();
+    history.cursor = None;
+    if line.is_empty() {
+        return;
+    }
+    if history.lines.last() != Some(&line) {
+        history.lines.push(line.clone());
+    }
+    cmd.queue(move |world: &mut World| run_console_line(world, &line));
+}
+
+fn console_history(
+    key_input: Res<ButtonInput<KeyCode>>,
+    mut history: ResMut<ConsoleHistory>,
+    mut input: Query<&mut TextInputValue, With<ConsoleInput>>,
+) {
+    let up = key_input.just_pressed(KeyCode::ArrowUp);
+    let down = key_input.just_pressed(KeyCode::ArrowDown);
+    if !up && !down || history.lines.is_empty() {
+        return;
+    }
+    let last = history.lines.len() - 1;
+    history.cursor = match (history.cursor, up) {
+        (None, true) => Some(last),
+        (None, false) => None,
+        (Some(cursor), true) => Some(cursor.saturating_sub(1)),
+        (Some(cursor), false) => (cursor < last).then_some(cursor + 1),
+    };
+    let line = history
+        .cursor
+        .map(|cursor| history.lines[cursor].clone())
+        .unwrap_or_default();
+    for mut value in input.iter_mut() {
+        value.0 = line.clone();
+    }
+}
+
+fn console_completion(
+    key_input: Res<ButtonInput<KeyCode>>,
+    commands: Res<ConsoleCommands>,
+    registry: Res<AppTypeRegistry>,
+    mut input: Query<&mut TextInputValue, With<ConsoleInput>>,
+    mut debug_log: ResMut<DebugLog>,
+) {
+    if !key_input.just_pressed(KeyCode::Tab) {
+        return;
+    }
+    let Ok(mut value) = input.get_single_mut() else {
+        return;
+    };
+    let line = value.0.replace('\t', "");
+    let (head, word) = line.rsplit_once(' ').unwrap_or(("", line.as_str()));
+    let candidates = if head.is_empty() {
+        commands
+            .names()
+            .filter(|name| name.starts_with(word))
+            .map(|name| name.to_string())
+            .collect::<Vec<_>>()
+    } else if matches!(head, "get" | "set") && !word.contains('.') {
+        resource_names(&registry.read())
+            .into_iter()
+            .filter(|name| name.to_lowercase().starts_with(&word.to_lowercase()))
+            .collect()
+    } else {
+        vec![]
+    };
+    let completed = match candidates.as_slice() {
+        [] => word.to_string(),
+        [candidate] if head.is_empty() => format!("{} ", candidate),
+        [candidate] => candidate.clone(),
+        [first, rest @ ..] => {
+            debug_log.log(Level::Info, "console", candidates.join("  "));
+            rest.iter().fold(first.clone(), |prefix, candidate| {
+                prefix
+                    .chars()
+                    .zip(candidate.chars())
+                    .take_while(|(a, b)| a == b)
+                    .map(|(a, _)| a)
+                    .collect()
+            })
+        }
+    };
+    let completed = if head.is_empty() {
+        completed
+    } else {
+        format!("{} {}", head, completed)
+    };
+    if value.0 != completed {
+        value.0 = completed;
+    }
+}
+
+fn resource_names(registry: &TypeRegistry) -> Vec<String> {
+    let mut names = registry
+        .iter()
+        .filter(|registration| registration.data::<ReflectResource>().is_some())
+        .map(|registration| {
+            registration
+                .type_info()
+                .type_path_table()
+                .short_path()
+                .to_string()
+        })
+        .collect::<Vec<_>>();
+    names.sort();
+    names
+}
+
+fn find_resource(registry: &TypeRegistry, name: &str) -> Result<ReflectResource, String> {
+    registry
+        .iter()
+        .filter(|registration| {
+            registration
+                .type_info()
+                .type_path_table()
+                .short_path()
+                .eq_ignore_ascii_case(name)
+        })
+        .find_map(|registration| registration.data::<ReflectResource>().cloned())
+        .ok_or_else(|| format!("unknown resource '{}'", name))
+}
+
+fn help(In(_): In<ConsoleArgs>, commands: Res<ConsoleCommands>) -> ConsoleResult {
+    Ok(commands
+        .0
+        .iter()
+        .map(|(name, command)| format!("{} - {}", name, command.help))
+        .collect::<Vec<_>>()
+        .join("\n"))
+}
+
+fn clear(In(_): In<ConsoleArgs>, mut debug_log: ResMut<DebugLog>) -> ConsoleResult {
+    debug_log.clear();
+    Ok(String::new())
+}
+
+fn quit(In(_): In<ConsoleArgs>, mut exit: EventWriter<AppExit>) -> ConsoleResult {
+    exit.send(AppExit::Success);
+    Ok(String::new())
+}
+
+fn timescale(In(args): In<ConsoleArgs>, mut time: ResMut<Time<Virtual>>) -> ConsoleResult {
+    if !args.is_empty() {
+        let speed = args.get::<f32>(0)?;
+        if !speed.is_finite() || speed < 0. {
+            return Err(format!("speed must be positive, got {}", speed));
+        }
+        time.set_relative_speed(speed);
+    }
+    Ok(format!("timescale = {}", time.relative_speed()))
+}
+
+fn get_resource(In(args): In<ConsoleArgs>, world: &mut World) -> ConsoleResult {
+    let target = args.get::<String>(0)?;
+    let (name, path) = target.split_once('.').unwrap_or((&target, ""));
+    let registry = world.resource::<AppTypeRegistry>().clone();
+    let resource = find_resource(&registry.read(), name)?;
+    let resource = resource
+        .reflect(world)
+        .ok_or_else(|| format!("resource '{}' doesn't exist", name))?;
+    let value = if path.is_empty() {
+        resource.as_partial_reflect()
+    } else {
+        resource.reflect_path(path).map_err(|err| err.to_string())?
+    };
+    Ok(format!("{} = {}", target, format_reflect(value)))
+}
+
+fn set_resource(In(args): In<ConsoleArgs>, world: &mut World) -> ConsoleResult {
+    let target = args.get::<String>(0)?;
+    let value = args.rest(1);
+    if value.is_empty() {
+        return Err("missing value".to_string());
+    }
+    let (name, path) = target.split_once('.').unwrap_or((&target, ""));
+    let registry = world.resource::<AppTypeRegistry>().clone();
+    let resource = find_resource(&registry.read(), name)?;
+    let mut resource = resource
+        .reflect_mut(world)
+        .ok_or_else(|| format!("resource '{}' doesn't exist", name))?;
+    let field = if path.is_empty() {
+        resource.as_partial_reflect_mut()
+    } else {
+        resource
+            .reflect_path_mut(path)
+            .map_err(|err| err.to_string())?
+    };
+    set_from_str(field, &value)?;
+    Ok(format!("{} = {}", target, format_reflect(field)))
+}
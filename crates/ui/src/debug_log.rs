@@ -0,0 +1,460 @@
+use std::{
+    collections::VecDeque,
+    fmt::{self, Display},
+    str::FromStr,
+    sync::mpsc,
+    time::Duration,
+};
+
+use bevy::{
+    log::{
+        BoxedLayer,
+        tracing_subscriber::{self, Layer},
+    },
+    prelude::*,
+    utils::tracing::{self, Subscriber},
+};
+use bevy_simple_text_input::TextInputValue;
+
+use crate::{
+    InputField, InputFieldLabel, InputFieldType, InputUISubmitEvent, InputUInitialValue, MaxHeight,
+    MaxWidth, ScrollArea, ScrollExtent, TextUI, Toasts,
+};
+
+pub struct DebugLogPlugin;
+
+impl Plugin for DebugLogPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            Update,
+            (tick_debug_log, update_debug_log_view)
+                .chain()
+                .run_if(resource_exists::<DebugLog>),
+        )
+        .add_observer(create_debug_log_view)
+        .add_observer(debug_log_filter);
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Default)]
+pub enum Level {
+    Trace,
+    Debug,
+    #[default]
+    Info,
+    Warn,
+    Error,
+}
+
+impl Level {
+    pub fn color(&self) -> Color {
+        match self {
+            Level::Trace => Color::srgb(0.6, 0.5, 0.8),
+            Level::Debug => Color::srgb(0.5, 0.7, 1.),
+            Level::Info => Color::WHITE,
+            Level::Warn => Color::srgb(1., 0.7, 0.3),
+            Level::Error => Color::srgb(1., 0.4, 0.4),
+        }
+    }
+}
+
+impl From<&tracing::Level> for Level {
+    fn from(level: &tracing::Level) -> Self {
+        match *level {
+            tracing::Level::TRACE => Level::Trace,
+            tracing::Level::DEBUG => Level::Debug,
+            tracing::Level::INFO => Level::Info,
+            tracing::Level::WARN => Level::Warn,
+            tracing::Level::ERROR => Level::Error,
+        }
+    }
+}
+
+impl FromStr for Level {
+    type Err = &'static str;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "trace" => Ok(Level::Trace),
+            "debug" => Ok(Level::Debug),
+            "info" => Ok(Level::Info),
+            "warn" => Ok(Level::Warn),
+            "error" => Ok(Level::Error),
+            _ => Err("invalid level"),
+        }
+    }
+}
+
+impl Display for Level {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Level::Trace => write!(f, "trace"),
+            Level::Debug => write!(f, "debug"),
+            Level::Info => write!(f, "info"),
+            Level::Warn => write!(f, "warn"),
+            Level::Error => write!(f, "error"),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct LogEntry {
+    pub level: Level,
+    pub timestamp: f64,
+    pub source: String,
+    pub category: Option<String>,
+    pub message: String,
+}
+
+impl LogEntry {
+    pub fn new(level: Level, message: impl Into<String>) -> Self {
+        Self {
+            level,
+            timestamp: 0.,
+            source: String::from("app"),
+            category: None,
+            message: message.into(),
+        }
+    }
+
+    pub fn with_source(mut self, source: impl Into<String>) -> Self {
+        self.source = source.into();
+        self
+    }
+
+    pub fn with_category(mut self, category: impl Into<String>) -> Self {
+        self.category = Some(category.into());
+        self
+    }
+}
+
+impl Display for LogEntry {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "[{:.2}] {:5} {}",
+            self.timestamp, self.level, self.source
+        )?;
+        if let Some(category) = &self.category {
+            write!(f, " [{}]", category)?;
+        }
+        write!(f, ": {}", self.message)
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct DebugLogFilter {
+    pub min_level: Level,
+    pub category: Option<String>,
+    pub search: String,
+}
+
+impl DebugLogFilter {
+    pub fn matches(&self, entry: &LogEntry) -> bool {
+        entry.level >= self.min_level
+            && self
+                .category
+                .as_ref()
+                .is_none_or(|category| entry.category.as_ref() == Some(category))
+            && (self.search.is_empty()
+                || entry
+                    .message
+                    .to_lowercase()
+                    .contains(&self.search.to_lowercase()))
+    }
+}
+
+#[derive(Resource)]
+pub struct DebugLog {
+    entries: VecDeque<LogEntry>,
+    capacity: usize,
+    pub filter: DebugLogFilter,
+    elapsed: f64,
+    timer: Timer,
+    ready_push: bool,
+}
+
+impl DebugLog {
+    pub fn new() -> Self {
+        Self::with_capacity(200)
+    }
+
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self {
+            entries: VecDeque::with_capacity(capacity),
+            capacity: capacity.max(1),
+            filter: DebugLogFilter::default(),
+            elapsed: 0.,
+            timer: Timer::new(Duration::from_secs_f32(0.3), TimerMode::Repeating),
+            ready_push: false,
+        }
+    }
+
+    pub fn push(&mut self, content: impl Into<String>) {
+        self.push_entry(LogEntry::new(Level::Info, content));
+    }
+
+    #[allow(unused)]
+    pub fn push_timed(&mut self, content: impl Into<String>) {
+        if self.ready_push {
+            self.push(content);
+            self.ready_push = false;
+        }
+    }
+
+    pub fn log(&mut self, level: Level, source: impl Into<String>, message: impl Into<String>) {
+        self.push_entry(LogEntry::new(level, message).with_source(source));
+    }
+
+    pub fn push_entry(&mut self, mut entry: LogEntry) {
+        entry.timestamp = self.elapsed;
+        if self.entries.len() >= self.capacity {
+            self.entries.pop_front();
+        }
+        self.entries.push_back(entry);
+    }
+
+    pub fn entries(&self) -> impl Iterator<Item = &LogEntry> {
+        self.entries.iter()
+    }
+
+    pub fn filtered(&self) -> impl Iterator<Item = &LogEntry> {
+        self.entries
+            .iter()
+            .filter(|entry| self.filter.matches(entry))
+    }
+
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    pub fn clear(&mut self) {
+        self.entries.clear();
+    }
+
+    fn tick(&mut self, time: &Time) {
+        self.elapsed = time.elapsed_secs_f64();
+        if self.timer.tick(time.delta()).just_finished() && !self.ready_push {
+            self.ready_push = true;
+        }
+    }
+}
+
+impl Default for DebugLog {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+fn tick_debug_log(time: Res<Time>, mut debug_log: ResMut<DebugLog>) {
+    // Ticking alone shouldn't make the view think there are new entries
+    debug_log.bypass_change_detection().tick(&time);
+}
+
+/// Filterable, scrollable view of the [`DebugLog`] entries.
+#[derive(Component)]
+#[require(Node)]
+pub struct DebugLogView;
+
+#[derive(Component)]
+struct DebugLogRows(Entity);
+
+#[derive(Component)]
+struct DebugLogRow;
+
+fn create_debug_log_view(
+    trigger: Trigger<OnAdd, DebugLogView>,
+    mut cmd: Commands,
+    max_height: Query<&MaxHeight>,
+) {
+    let max_height = max_height
+        .get(trigger.entity())
+        .cloned()
+        .unwrap_or(MaxHeight(Val::Px(150.)));
+    let mut rows = Entity::PLACEHOLDER;
+    cmd.entity(trigger.entity())
+        .insert(Node {
+            flex_direction: FlexDirection::Column,
+            row_gap: Val::Px(3.),
+            ..default()
+        })
+        .with_children(|parent| {
+            for (label, name, value, width) in [
+                ("level", "debug_log:level", "info", 45.),
+                ("category", "debug_log:category", "", 85.),
+                ("search", "debug_log:search", "", 85.),
+            ] {
+                parent.spawn((
+                    InputField,
+                    InputFieldLabel::new(label),
+                    InputUInitialValue(value.to_string()),
+                    InputFieldType::String,
+                    MaxWidth(Val::Px(width)),
+                    Name::new(name),
+                ));
+            }
+            rows = parent.spawn((ScrollArea, max_height)).id();
+        });
+    cmd.entity(trigger.entity()).insert(DebugLogRows(rows));
+}
+
+fn debug_log_filter(
+    trigger: Trigger<InputUISubmitEvent>,
+    mut input: Query<(&mut TextInputValue, &Name)>,
+    debug_log: Option<ResMut<DebugLog>>,
+) {
+    let (Ok((mut value, name)), Some(mut debug_log)) = (input.get_mut(trigger.entity()), debug_log)
+    else {
+        return;
+    };
+    match name.as_str() {
+        "debug_log:level" => match Level::from_str(value.0.trim()) {
+            Ok(level) => debug_log.filter.min_level = level,
+            Err(_) => value.0 = debug_log.filter.min_level.to_string(),
+        },
+        "debug_log:category" => {
+            let category = value.0.trim();
+            debug_log.filter.category = (!category.is_empty()).then(|| category.to_string());
+        }
+        "debug_log:search" => debug_log.filter.search = value.0.trim().to_string(),
+        _ => {}
+    }
+}
+
+fn update_debug_log_view(
+    mut cmd: Commands,
+    debug_log: Res<DebugLog>,
+    view: Query<&DebugLogRows>,
+    mut list: Query<(&mut ScrollPosition, &ScrollExtent, Option<&Children>)>,
+    mut rows: Query<(&mut Text, &mut TextColor, &mut Node), With<DebugLogRow>>,
+) {
+    if !debug_log.is_changed() {
+        return;
+    }
+    let lines = debug_log
+        .filtered()
+        .map(|entry| (entry.to_string(), entry.level.color()))
+        .collect::<Vec<_>>();
+    for DebugLogRows(list_entity) in view.iter() {
+        let Ok((mut scroll, extent, children)) = list.get_mut(*list_entity) else {
+            continue;
+        };
+        let existing = children
+            .map(|children| {
+                children
+                    .iter()
+                    .filter(|child| rows.contains(**child))
+                    .copied()
+                    .collect::<Vec<_>>()
+            })
+            .unwrap_or_default();
+        // Rows are reused instead of respawned, so a full log only costs text
+        // updates when new entries push the old ones out.
+        for (i, (line, color)) in lines.iter().enumerate() {
+            match existing.get(i) {
+                Some(row) => {
+                    let (mut text, mut text_color, mut node) = rows.get_mut(*row).unwrap();
+                    if text.0 != *line {
+                        text.0 = line.clone();
+                    }
+                    text_color.set_if_neq(TextColor(*color));
+                    if node.display != Display::Flex {
+                        node.display = Display::Flex;
+                    }
+                }
+                None => {
+                    cmd.spawn((DebugLogRow, TextUI::new(line.clone()), TextColor(*color)))
+                        .set_parent(*list_entity);
+                }
+            }
+        }
+        for row in existing.iter().skip(lines.len()) {
+            let (.., mut node) = rows.get_mut(*row).unwrap();
+            if node.display != Display::None {
+                node.display = Display::None;
+            }
+        }
+        // Follow new entries unless the user scrolled up to read older ones
+        if scroll.offset_y >= extent.max_offset() - 1. {
+            scroll.offset_y = extent.content;
+        }
+    }
+}
+
+#[derive(Deref, DerefMut)]
+struct CapturedLogEntries(mpsc::Receiver<LogEntry>);
+
+/// Forwards `tracing` events (`info!`, `warn!`, ...) into [`DebugLog`], use it
+/// as `LogPlugin::custom_layer`. A `category` field on the event is used as
+/// the entry category, e.g. `warn!(category = "config", "...")`.
+pub fn debug_log_layer(app: &mut App) -> Option<BoxedLayer> {
+    let (sender, receiver) = mpsc::channel();
+    app.insert_non_send_resource(CapturedLogEntries(receiver))
+        .add_systems(PreUpdate, transfer_log_entries);
+    Some(CaptureLayer { sender }.boxed())
+}
+
+fn transfer_log_entries(
+    receiver: NonSend<CapturedLogEntries>,
+    mut debug_log: Option<ResMut<DebugLog>>,
+    mut toasts: Option<ResMut<Toasts>>,
+) {
+    // Entries are drained even without a DebugLog so they don't pile up in
+    // the channel
+    for entry in receiver.try_iter() {
+        if let Some(toasts) = toasts.as_mut() {
+            toasts.push_log_entry(&entry);
+        }
+        if let Some(debug_log) = debug_log.as_mut() {
+            debug_log.push_entry(entry);
+        }
+    }
+}
+
+struct CaptureLayer {
+    sender: mpsc::Sender<LogEntry>,
+}
+
+impl<S: Subscriber> Layer<S> for CaptureLayer {
+    fn on_event(
+        &self,
+        event: &tracing::Event<'_>,
+        _ctx: tracing_subscriber::layer::Context<'_, S>,
+    ) {
+        let mut visitor = CaptureVisitor::default();
+        event.record(&mut visitor);
+        let Some(message) = visitor.message else {
+            return;
+        };
+        let metadata = event.metadata();
+        let mut entry =
+            LogEntry::new(metadata.level().into(), message).with_source(metadata.target());
+        entry.category = visitor.category;
+        // The receiver is gone once the app exits, nothing left to log into
+        let _ = self.sender.send(entry);
+    }
+}
+
+#[derive(Default)]
+struct CaptureVisitor {
+    message: Option<String>,
+    category: Option<String>,
+}
+
+impl tracing::field::Visit for CaptureVisitor {
+    fn record_str(&mut self, field: &tracing::field::Field, value: &str) {
+        match field.name() {
+            "message" => self.message = Some(value.to_string()),
+            "category" => self.category = Some(value.to_string()),
+            _ => {}
+        }
+    }
+
+    fn record_debug(&mut self, field: &tracing::field::Field, value: &dyn fmt::Debug) {
+        match field.name() {
+            "message" => self.message = Some(format!("{:?}", value)),
+            "category" => self.category = Some(format!("{:?}", value)),
+            _ => {}
+        }
+    }
+}
@@ -0,0 +1,476 @@
+use std::{any::TypeId, collections::HashMap, time::Duration};
+
+use bevy::{prelude::*, time::common_conditions::on_timer};
+use bevy_simple_text_input::{TextInputInactive, TextInputValue};
+
+use crate::{
+    DebugLog, Header, InputField, InputFieldLabel, InputFieldType, InputUISubmitEvent,
+    InputUInitialValue, Level, MaxHeight, MaxWidth, ScrollArea, TextUI, Toasts,
+    reflect_value::{leaf_fields, set_from_str},
+};
+
+pub struct InspectorPlugin;
+
+impl Plugin for InspectorPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<InspectedEntity>()
+            .init_resource::<InspectorComponents>()
+            .init_resource::<InspectorSearch>()
+            .init_resource::<InspectorSnapshot>()
+            .add_systems(
+                Update,
+                (
+                    update_entity_list.run_if(
+                        on_timer(Duration::from_millis(500))
+                            .or(resource_changed::<InspectedEntity>)
+                            .or(resource_changed::<InspectorSearch>),
+                    ),
+                    (snapshot_inspected, update_inspector_fields)
+                        .chain()
+                        .run_if(
+                            on_timer(Duration::from_millis(100))
+                                .or(resource_changed::<InspectedEntity>),
+                        ),
+                ),
+            )
+            .add_observer(create_inspector_view)
+            .add_observer(inspector_search)
+            .add_observer(edit_inspector_field);
+    }
+}
+
+/// Entity shown by every [`InspectorView`].
+#[derive(Resource, Default)]
+pub struct InspectedEntity(pub Option<Entity>);
+
+/// Short type names of the components to show, e.g. `"Transform"`. Every
+/// reflected component is shown when empty.
+#[derive(Resource, Default)]
+pub struct InspectorComponents(pub Vec<String>);
+
+/// Searchable list of named entities and the reflected components of the
+/// [`InspectedEntity`], field values can be edited in place.
+#[derive(Component)]
+#[require(Node)]
+pub struct InspectorView;
+
+#[derive(Component)]
+struct InspectorParts {
+    list: Entity,
+    title: Entity,
+    fields: Entity,
+}
+
+#[derive(Component)]
+struct EntityListRow(Entity);
+
+#[derive(Component, Clone)]
+struct InspectorField {
+    entity: Entity,
+    type_id: TypeId,
+    path: String,
+}
+
+#[derive(Component, Default)]
+struct InspectorLayout(Vec<(TypeId, String)>);
+
+#[derive(Resource, Default)]
+struct InspectorSearch(String);
+
+#[derive(PartialEq)]
+struct InspectedComponent {
+    name: String,
+    type_id: TypeId,
+    fields: Vec<(String, String)>,
+}
+
+#[derive(Resource, Default, PartialEq)]
+struct InspectorSnapshot {
+    entity: Option<Entity>,
+    label: String,
+    components: Vec<InspectedComponent>,
+}
+
+fn entity_label(entity: Entity, name: Option<&Name>) -> String {
+    match name {
+        Some(name) => format!("{} ({})", name, entity),
+        None => entity.to_string(),
+    }
+}
+
+fn create_inspector_view(
+    trigger: Trigger<OnAdd, InspectorView>,
+    mut cmd: Commands,
+    max_height: Query<&MaxHeight>,
+) {
+    let max_height = max_height
+        .get(trigger.entity())
+        .cloned()
+        .unwrap_or(MaxHeight(Val::Px(150.)));
+    let (mut list, mut title, mut fields) = (
+        Entity::PLACEHOLDER,
+        Entity::PLACEHOLDER,
+        Entity::PLACEHOLDER,
+    );
+    cmd.entity(trigger.entity())
+        .insert(Node {
+            flex_direction: FlexDirection::Column,
+            row_gap: Val::Px(3.),
+            ..default()
+        })
+        .with_children(|parent| {
+            parent.spawn((
+                InputField,
+                InputFieldLabel::new("search"),
+                InputUInitialValue::default(),
+                InputFieldType::String,
+                MaxWidth(Val::Px(85.)),
+                Name::new("inspector:search"),
+            ));
+            list = parent.spawn((ScrollArea, max_height.clone())).id();
+            title = parent.spawn(TextUI::new("no entity selected")).id();
+            // Fields get respawned, so they live in their own node to keep
+            // the scrollbar of the area around.
+            parent
+                .spawn((ScrollArea, max_height))
+                .with_children(|parent| {
+                    fields = parent
+                        .spawn((
+                            Node {
+                                flex_direction: FlexDirection::Column,
+                                row_gap: Val::Px(3.),
+                                ..default()
+                            },
+                            InspectorLayout::default(),
+                        ))
+                        .id();
+                });
+        });
+    cmd.entity(trigger.entity()).insert(InspectorParts {
+        list,
+        title,
+        fields,
+    });
+}
+
+fn inspector_search(
+    trigger: Trigger<InputUISubmitEvent>,
+    input: Query<(&TextInputValue, &Name)>,
+    mut search: ResMut<InspectorSearch>,
+) {
+    if let Ok((value, name)) = input.get(trigger.entity()) {
+        if name.as_str() == "inspector:search" {
+            search.0 = value.0.trim().to_lowercase();
+        }
+    }
+}
+
+fn update_entity_list(
+    mut cmd: Commands,
+    named: Query<(Entity, &Name), Without<Node>>,
+    parents: Query<&Parent>,
+    children: Query<&Children>,
+    search: Res<InspectorSearch>,
+    inspected: Res<InspectedEntity>,
+    view: Query<&InspectorParts>,
+    mut rows: Query<(&mut EntityListRow, &mut Text, &mut TextColor, &mut Node)>,
+) {
+    let mut roots = named
+        .iter()
+        .map(|(entity, _)| entity)
+        .filter(|entity| {
+            !parents
+                .iter_ancestors(*entity)
+                .any(|ancestor| named.contains(ancestor))
+        })
+        .collect::<Vec<_>>();
+    roots.sort();
+    // Depth first so children are listed right under their named ancestor
+    let mut lines = vec![];
+    let mut stack = roots
+        .into_iter()
+        .rev()
+        .map(|entity| (entity, 0))
+        .collect::<Vec<_>>();
+    while let Some((entity, depth)) = stack.pop() {
+        let name = named.get(entity).ok().map(|(_, name)| name);
+        if let Some(name) = name {
+            if search.0.is_empty() || name.to_lowercase().contains(&search.0) {
+                lines.push((
+                    entity,
+                    format!("{}{}", "  ".repeat(depth), entity_label(entity, Some(name))),
+                ));
+            }
+        }
+        let depth = depth + name.is_some() as usize;
+        if let Ok(children) = children.get(entity) {
+            stack.extend(children.iter().rev().map(|child| (*child, depth)));
+        }
+    }
+
+    for parts in view.iter() {
+        let existing = children
+            .get(parts.list)
+            .map(|children| {
+                children
+                    .iter()
+                    .filter(|child| rows.contains(**child))
+                    .copied()
+                    .collect::<Vec<_>>()
+            })
+            .unwrap_or_default();
+        for (i, (entity, line)) in lines.iter().enumerate() {
+            let color = if inspected.0 == Some(*entity) {
+                Color::srgb(1., 0.8, 0.3)
+            } else {
+                Color::WHITE
+            };
+            match existing.get(i) {
+                Some(row) => {
+                    let (mut row, mut text, mut text_color, mut node) = rows.get_mut(*row).unwrap();
+                    row.0 = *entity;
+                    if text.0 != *line {
+                        text.0 = line.clone();
+                    }
+                    text_color.set_if_neq(TextColor(color));
+                    if node.display != Display::Flex {
+                        node.display = Display::Flex;
+                    }
+                }
+                None => {
+                    cmd.spawn((
+                        EntityListRow(*entity),
+                        TextUI::new(line.clone()),
+                        TextColor(color),
+                    ))
+                    .set_parent(parts.list)
+                    .observe(select_entity_row);
+                }
+            }
+        }
+        for row in existing.iter().skip(lines.len()) {
+            let (.., mut node) = rows.get_mut(*row).unwrap();
+            if node.display != Display::None {
+                node.display = Display::None;
+            }
+        }
+    }
+}
+
+fn select_entity_row(
+    trigger: Trigger<Pointer<Click>>,
+    row: Query<&EntityListRow>,
+    mut inspected: ResMut<InspectedEntity>,
+) {
+    if let Ok(row) = row.get(trigger.entity()) {
+        inspected.0 = Some(row.0);
+    }
+}
+
+fn inspect(world: &World, entity: Entity, only: &[String]) -> Vec<InspectedComponent> {
+    let Ok(entity_ref) = world.get_entity(entity) else {
+        return vec![];
+    };
+    let registry = world.resource::<AppTypeRegistry>().read();
+    let mut components = entity_ref
+        .archetype()
+        .components()
+        .filter_map(|id| world.components().get_info(id)?.type_id())
+        .filter_map(|type_id| {
+            let registration = registry.get(type_id)?;
+            let name = registration.type_info().type_path_table().short_path();
+            if !only.is_empty() && !only.iter().any(|only| only == name) {
+                return None;
+            }
+            let value = registration
+                .data::<ReflectComponent>()?
+                .reflect(entity_ref)?;
+            let mut fields = vec![];
+            leaf_fields(value.as_partial_reflect(), String::new(), &mut fields);
+            Some(InspectedComponent {
+                name: name.to_string(),
+                type_id,
+                fields,
+            })
+        })
+        .collect::<Vec<_>>();
+    components.sort_by(|a, b| a.name.cmp(&b.name));
+    components
+}
+
+fn snapshot_inspected(world: &mut World) {
+    let mut entity = world.resource::<InspectedEntity>().0;
+    if entity.is_some_and(|entity| world.get_entity(entity).is_err()) {
+        world.resource_mut::<InspectedEntity>().0 = None;
+        entity = None;
+    }
+    let only = world.resource::<InspectorComponents>().0.clone();
+    let snapshot = match entity {
+        Some(entity) => InspectorSnapshot {
+            entity: Some(entity),
+            label: entity_label(entity, world.get::<Name>(entity)),
+            components: inspect(world, entity, &only),
+        },
+        None => InspectorSnapshot {
+            entity: None,
+            label: "no entity selected".to_string(),
+            components: vec![],
+        },
+    };
+    world
+        .resource_mut::<InspectorSnapshot>()
+        .set_if_neq(snapshot);
+}
+
+fn update_inspector_fields(
+    mut cmd: Commands,
+    snapshot: Res<InspectorSnapshot>,
+    view: Query<&InspectorParts>,
+    mut layout: Query<&mut InspectorLayout>,
+    mut text: Query<&mut Text>,
+    fields: Query<(&InspectorField, &Children)>,
+    mut inputs: Query<(&mut TextInputValue, &TextInputInactive)>,
+    children: Query<&Children>,
+) {
+    if !snapshot.is_changed() {
+        return;
+    }
+    let expected = snapshot
+        .components
+        .iter()
+        .flat_map(|component| {
+            std::iter::once((component.type_id, String::new())).chain(
+                component
+                    .fields
+                    .iter()
+                    .map(|(path, _)| (component.type_id, path.clone())),
+            )
+        })
+        .collect::<Vec<_>>();
+    let values = snapshot
+        .components
+        .iter()
+        .flat_map(|component| {
+            component
+                .fields
+                .iter()
+                .map(|(path, value)| ((component.type_id, path.as_str()), value))
+        })
+        .collect::<HashMap<_, _>>();
+
+    for parts in view.iter() {
+        if let Ok(mut text) = text.get_mut(parts.title) {
+            if text.0 != snapshot.label {
+                text.0 = snapshot.label.clone();
+            }
+        }
+        let Ok(mut layout) = layout.get_mut(parts.fields) else {
+            continue;
+        };
+        if layout.0 != expected {
+            layout.0 = expected.clone();
+            cmd.entity(parts.fields)
+                .despawn_descendants()
+                .with_children(|parent| {
+                    let Some(entity) = snapshot.entity else {
+                        return;
+                    };
+                    for component in snapshot.components.iter() {
+                        parent.spawn(Header::new(component.name.clone()));
+                        for (path, value) in component.fields.iter() {
+                            let label = match path.strip_prefix('.') {
+                                Some(label) => label,
+                                None => "value",
+                            };
+                            parent.spawn((
+                                InputField,
+                                InputFieldLabel::new(label),
+                                InputUInitialValue(value.clone()),
+                                InputFieldType::String,
+                                MaxWidth(Val::Px(85.)),
+                                Name::new("inspector:field"),
+                                InspectorField {
+                                    entity,
+                                    type_id: component.type_id,
+                                    path: path.clone(),
+                                },
+                            ));
+                        }
+                    }
+                });
+            continue;
+        }
+        let Ok(rows) = children.get(parts.fields) else {
+            continue;
+        };
+        for (field, field_children) in fields.iter_many(rows) {
+            let Some(value) = values.get(&(field.type_id, field.path.as_str())) else {
+                continue;
+            };
+            let mut inputs = inputs.iter_many_mut(field_children);
+            while let Some((mut input, inactive)) = inputs.fetch_next() {
+                // Don't overwrite what the user is typing
+                if inactive.0 && input.0 != **value {
+                    input.0 = (*value).clone();
+                }
+            }
+        }
+    }
+}
+
+fn edit_inspector_field(
+    trigger: Trigger<InputUISubmitEvent>,
+    mut cmd: Commands,
+    input: Query<(&TextInputValue, &Parent)>,
+    field: Query<&InspectorField>,
+) {
+    let Ok((value, parent)) = input.get(trigger.entity()) else {
+        return;
+    };
+    let Ok(field) = field.get(parent.get()) else {
+        return;
+    };
+    let field = field.clone();
+    let value = value.0.trim().to_string();
+    cmd.queue(move |world: &mut World| {
+        if let Err(err) = apply_inspector_edit(world, &field, &value) {
+            // warn! reaches the toasts by itself when they forward logs
+            match world.get_resource_mut::<DebugLog>() {
+                Some(mut debug_log) => debug_log.log(Level::Warn, "inspector", err.clone()),
+                None => {
+                    warn!("{}", err);
+                    return;
+                }
+            }
+            if let Some(mut toasts) = world.get_resource_mut::<Toasts>() {
+                toasts.push(Level::Warn, err);
+            }
+        }
+    });
+}
+
+fn apply_inspector_edit(
+    world: &mut World,
+    field: &InspectorField,
+    value: &str,
+) -> Result<(), String> {
+    let registry = world.resource::<AppTypeRegistry>().clone();
+    let registry = registry.read();
+    let reflect_component = registry
+        .get_type_data::<ReflectComponent>(field.type_id)
+        .ok_or("component isn't reflected")?;
+    let mut entity = world
+        .get_entity_mut(field.entity)
+        .map_err(|err| err.to_string())?;
+    let mut component = reflect_component
+        .reflect_mut(&mut entity)
+        .ok_or("component was removed")?;
+    let target = if field.path.is_empty() {
+        component.as_partial_reflect_mut()
+    } else {
+        component
+            .reflect_path_mut(field.path.as_str())
+            .map_err(|err| err.to_string())?
+    };
+    set_from_str(target, value)
+}
@@ -0,0 +1,462 @@
+use std::{collections::BTreeMap, fmt, fs, path::PathBuf};
+
+use bevy::{ecs::system::SystemParam, input::InputSystem, prelude::*};
+use serde::{Deserialize, Serialize};
+
+use crate::{ButtonPressed, Level, TextButton, TextUI, Toasts, Tooltip};
+
+pub struct KeyBindingsPlugin;
+
+impl Plugin for KeyBindingsPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<KeyBindings>()
+            .init_resource::<KeyCapture>()
+            .add_systems(PreUpdate, capture_binding.after(InputSystem))
+            .add_systems(Update, update_key_bindings_view)
+            .add_observer(create_key_bindings_view);
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum Binding {
+    Key(KeyCode),
+    Mouse(MouseButton),
+}
+
+impl From<KeyCode> for Binding {
+    fn from(key: KeyCode) -> Self {
+        Binding::Key(key)
+    }
+}
+
+impl From<MouseButton> for Binding {
+    fn from(button: MouseButton) -> Self {
+        Binding::Mouse(button)
+    }
+}
+
+impl fmt::Display for Binding {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Binding::Key(key) => {
+                let name = format!("{:?}", key);
+                let name = name
+                    .strip_prefix("Key")
+                    .or_else(|| name.strip_prefix("Digit"))
+                    .unwrap_or(&name);
+                write!(f, "{}", name)
+            }
+            Binding::Mouse(MouseButton::Other(button)) => write!(f, "Mouse {}", button),
+            Binding::Mouse(button) => write!(f, "Mouse {:?}", button),
+        }
+    }
+}
+
+pub trait KeyBindingsAppExt {
+    /// Registers an action, saved bindings take precedence over the defaults.
+    fn register_action(
+        &mut self,
+        name: impl Into<String>,
+        defaults: impl IntoIterator<Item = impl Into<Binding>>,
+    ) -> &mut Self;
+
+    /// Loads bindings from a TOML file, edits are saved back to it.
+    fn load_key_bindings(&mut self, path: impl Into<PathBuf>) -> &mut Self;
+}
+
+impl KeyBindingsAppExt for App {
+    fn register_action(
+        &mut self,
+        name: impl Into<String>,
+        defaults: impl IntoIterator<Item = impl Into<Binding>>,
+    ) -> &mut Self {
+        // Lets ActionInput run in apps without KeyBindingsPlugin
+        self.init_resource::<KeyCapture>();
+        self.world_mut()
+            .get_resource_or_init::<KeyBindings>()
+            .register(name, defaults.into_iter().map(Into::into).collect());
+        self
+    }
+
+    fn load_key_bindings(&mut self, path: impl Into<PathBuf>) -> &mut Self {
+        self.world_mut()
+            .get_resource_or_init::<KeyBindings>()
+            .load(path);
+        self
+    }
+}
+
+struct Action {
+    name: String,
+    defaults: Vec<Binding>,
+    bindings: Vec<Binding>,
+}
+
+/// Named actions and the keys or mouse buttons bound to them, read them
+/// through [`ActionInput`].
+#[derive(Resource, Default)]
+pub struct KeyBindings {
+    actions: Vec<Action>,
+    saved: BTreeMap<String, Vec<Binding>>,
+    path: Option<PathBuf>,
+}
+
+impl KeyBindings {
+    fn register(&mut self, name: impl Into<String>, defaults: Vec<Binding>) {
+        let name = name.into();
+        let bindings = self
+            .saved
+            .get(&name)
+            .cloned()
+            .unwrap_or_else(|| defaults.clone());
+        match self.actions.iter_mut().find(|action| action.name == name) {
+            Some(action) => {
+                action.defaults = defaults;
+                action.bindings = bindings;
+            }
+            None => self.actions.push(Action {
+                name,
+                defaults,
+                bindings,
+            }),
+        }
+    }
+
+    fn load(&mut self, path: impl Into<PathBuf>) {
+        let path = path.into();
+        self.saved = fs::read_to_string(&path)
+            .ok()
+            .and_then(|content| toml::from_str(&content).ok())
+            .unwrap_or_default();
+        for action in self.actions.iter_mut() {
+            if let Some(bindings) = self.saved.get(&action.name) {
+                action.bindings = bindings.clone();
+            }
+        }
+        self.path = Some(path);
+    }
+
+    pub fn actions(&self) -> impl Iterator<Item = (&str, &[Binding])> {
+        self.actions
+            .iter()
+            .map(|action| (action.name.as_str(), action.bindings.as_slice()))
+    }
+
+    pub fn bindings(&self, action: &str) -> &[Binding] {
+        self.actions
+            .iter()
+            .find(|registered| registered.name == action)
+            .map(|action| action.bindings.as_slice())
+            .unwrap_or_default()
+    }
+
+    pub fn set(&mut self, action: &str, bindings: Vec<Binding>) {
+        let Some(action) = self
+            .actions
+            .iter_mut()
+            .find(|registered| registered.name == action)
+        else {
+            return;
+        };
+        action.bindings = bindings;
+        self.saved
+            .insert(action.name.clone(), action.bindings.clone());
+        self.save();
+    }
+
+    pub fn reset(&mut self, action: &str) {
+        let defaults = self
+            .actions
+            .iter()
+            .find(|registered| registered.name == action)
+            .map(|action| action.defaults.clone());
+        if let Some(defaults) = defaults {
+            self.set(action, defaults);
+        }
+    }
+
+    /// Bindings of the action that other actions use too, with the name of
+    /// the other action.
+    pub fn conflicts(&self, action: &str) -> Vec<(Binding, &str)> {
+        self.bindings(action)
+            .iter()
+            .flat_map(|binding| {
+                self.actions
+                    .iter()
+                    .filter(move |other| other.name != action && other.bindings.contains(binding))
+                    .map(move |other| (*binding, other.name.as_str()))
+            })
+            .collect()
+    }
+
+    fn save(&self) {
+        let Some(path) = &self.path else {
+            return;
+        };
+        match toml::to_string(&self.saved) {
+            Ok(content) => {
+                if let Err(err) = fs::write(path, content) {
+                    warn!("Failed to save key bindings to {:?}: {}", path, err);
+                }
+            }
+            Err(err) => warn!("Failed to serialize key bindings: {}", err),
+        }
+    }
+}
+
+/// Binding being edited in a [`KeyBindingsView`], actions don't fire while
+/// it waits for input.
+#[derive(Resource, Default)]
+pub struct KeyCapture(Option<BindingSlot>);
+
+impl KeyCapture {
+    pub fn is_capturing(&self) -> bool {
+        self.0.is_some()
+    }
+}
+
+/// State of the actions, e.g. `input.just_pressed("fire")`.
+#[derive(SystemParam)]
+pub struct ActionInput<'w> {
+    bindings: Res<'w, KeyBindings>,
+    keys: Res<'w, ButtonInput<KeyCode>>,
+    mouse: Res<'w, ButtonInput<MouseButton>>,
+    capture: Res<'w, KeyCapture>,
+}
+
+impl ActionInput<'_> {
+    pub fn pressed(&self, action: &str) -> bool {
+        self.any(
+            action,
+            |keys, key| keys.pressed(key),
+            |mouse, button| mouse.pressed(button),
+        )
+    }
+
+    pub fn just_pressed(&self, action: &str) -> bool {
+        self.any(
+            action,
+            |keys, key| keys.just_pressed(key),
+            |mouse, button| mouse.just_pressed(button),
+        )
+    }
+
+    pub fn just_released(&self, action: &str) -> bool {
+        self.any(
+            action,
+            |keys, key| keys.just_released(key),
+            |mouse, button| mouse.just_released(button),
+        )
+    }
+
+    fn any(
+        &self,
+        action: &str,
+        key: fn(&ButtonInput<KeyCode>, KeyCode) -> bool,
+        button: fn(&ButtonInput<MouseButton>, MouseButton) -> bool,
+    ) -> bool {
+        !self.capture.is_capturing()
+            && self
+                .bindings
+                .bindings(action)
+                .iter()
+                .any(|binding| match binding {
+                    Binding::Key(code) => key(&self.keys, *code),
+                    Binding::Mouse(mouse_button) => button(&self.mouse, *mouse_button),
+                })
+    }
+}
+
+/// Run condition for systems driven by an action.
+pub fn action_pressed(action: &'static str) -> impl FnMut(ActionInput) -> bool + Clone {
+    move |input: ActionInput| input.pressed(action)
+}
+
+pub fn action_just_pressed(action: &'static str) -> impl FnMut(ActionInput) -> bool + Clone {
+    move |input: ActionInput| input.just_pressed(action)
+}
+
+/// Lists the registered actions. Clicking a binding captures the next key or
+/// mouse button to replace it, Backspace removes it and Escape cancels.
+/// Bindings shared by several actions are marked with "!".
+#[derive(Component)]
+#[require(Node)]
+pub struct KeyBindingsView;
+
+/// Binding of an action, `index` is `None` for a new binding.
+#[derive(Component, Clone, PartialEq)]
+struct BindingSlot {
+    action: String,
+    index: Option<usize>,
+}
+
+#[derive(Component)]
+struct ResetBindings;
+
+fn create_key_bindings_view(trigger: Trigger<OnAdd, KeyBindingsView>, mut cmd: Commands) {
+    cmd.entity(trigger.entity()).insert(Node {
+        flex_direction: FlexDirection::Column,
+        row_gap: Val::Px(3.),
+        ..default()
+    });
+}
+
+fn update_key_bindings_view(
+    mut cmd: Commands,
+    view: Query<Entity, With<KeyBindingsView>>,
+    new_view: Query<(), Added<KeyBindingsView>>,
+    bindings: Res<KeyBindings>,
+    capture: Res<KeyCapture>,
+) {
+    if !bindings.is_changed() && !capture.is_changed() && new_view.is_empty() {
+        return;
+    }
+    for view in view.iter() {
+        cmd.entity(view)
+            .despawn_descendants()
+            .with_children(|parent| {
+                for (action, action_bindings) in bindings.actions() {
+                    let conflicts = bindings.conflicts(action);
+                    parent
+                        .spawn(Node {
+                            flex_direction: FlexDirection::Row,
+                            column_gap: Val::Px(3.),
+                            ..default()
+                        })
+                        .with_children(|parent| {
+                            parent.spawn((TextUI::new(action), Node {
+                                flex_grow: 1.,
+                                margin: UiRect::right(Val::Px(3.)),
+                                ..default()
+                            }));
+                            let slots = action_bindings
+                                .iter()
+                                .map(Some)
+                                .enumerate()
+                                .chain([(action_bindings.len(), None)]);
+                            for (index, binding) in slots {
+                                let slot = BindingSlot {
+                                    action: action.to_string(),
+                                    index: binding.map(|_| index),
+                                };
+                                let others = conflicts
+                                    .iter()
+                                    .filter(|(conflict, _)| Some(conflict) == binding)
+                                    .map(|(_, other)| *other)
+                                    .collect::<Vec<_>>();
+                                let label = match (capture.0.as_ref() == Some(&slot), binding) {
+                                    (true, _) => "...".to_string(),
+                                    (false, Some(binding)) if !others.is_empty() => {
+                                        format!("! {}", binding)
+                                    }
+                                    (false, Some(binding)) => binding.to_string(),
+                                    (false, None) => "+".to_string(),
+                                };
+                                let mut button = parent.spawn((TextButton::new(label), slot));
+                                if !others.is_empty() {
+                                    button.insert(Tooltip::new(format!(
+                                        "Also bound to {}",
+                                        others.join(", ")
+                                    )));
+                                }
+                                button.observe(start_capture);
+                            }
+                        });
+                }
+                parent
+                    .spawn((TextButton::new("Reset all"), ResetBindings))
+                    .observe(reset_bindings);
+            });
+    }
+}
+
+fn start_capture(
+    trigger: Trigger<ButtonPressed>,
+    slot: Query<&BindingSlot>,
+    mut capture: ResMut<KeyCapture>,
+) {
+    if let Ok(slot) = slot.get(trigger.entity()) {
+        capture.0 = Some(slot.clone());
+    }
+}
+
+fn reset_bindings(_trigger: Trigger<ButtonPressed>, mut bindings: ResMut<KeyBindings>) {
+    let actions = bindings
+        .actions()
+        .map(|(action, _)| action.to_string())
+        .collect::<Vec<_>>();
+    for action in actions {
+        bindings.reset(&action);
+    }
+}
+
+fn capture_binding(
+    mut capture: ResMut<KeyCapture>,
+    mut keys: ResMut<ButtonInput<KeyCode>>,
+    mut mouse: ResMut<ButtonInput<MouseButton>>,
+    mut bindings: ResMut<KeyBindings>,
+    toasts: Option<ResMut<Toasts>>,
+) {
+    let Some(slot) = capture.0.clone() else {
+        return;
+    };
+    let key = keys.get_just_pressed().next().copied();
+    let button = mouse.get_just_pressed().next().copied();
+    // The captured input is consumed so it doesn't also reach the app
+    let binding = match (key, button) {
+        (Some(KeyCode::Escape), _) => {
+            keys.reset(KeyCode::Escape);
+            capture.0 = None;
+            return;
+        }
+        (Some(key @ (KeyCode::Backspace | KeyCode::Delete)), _) => {
+            keys.reset(key);
+            None
+        }
+        (Some(key), _) => {
+            keys.reset(key);
+            Some(Binding::Key(key))
+        }
+        (None, Some(button)) => {
+            mouse.reset(button);
+            Some(Binding::Mouse(button))
+        }
+        (None, None) => return,
+    };
+    capture.0 = None;
+
+    let mut action_bindings = bindings.bindings(&slot.action).to_vec();
+    match (slot.index, binding) {
+        (Some(index), Some(binding)) if index < action_bindings.len() => {
+            action_bindings[index] = binding
+        }
+        (Some(index), None) if index < action_bindings.len() => {
+            action_bindings.remove(index);
+        }
+        (Some(_), _) => {}
+        (None, Some(binding)) => action_bindings.push(binding),
+        (None, None) => {}
+    }
+    let mut seen = vec![];
+    action_bindings.retain(|binding| {
+        let new = !seen.contains(binding);
+        seen.push(*binding);
+        new
+    });
+    bindings.set(&slot.action, action_bindings);
+
+    if let (Some(binding), Some(mut toasts)) = (binding, toasts) {
+        let others = bindings
+            .conflicts(&slot.action)
+            .into_iter()
+            .filter(|(conflict, _)| *conflict == binding)
+            .map(|(_, other)| other)
+            .collect::<Vec<_>>();
+        if !others.is_empty() {
+            toasts.push(
+                Level::Warn,
+                format!("{} is also bound to {}", binding, others.join(", ")),
+            );
+        }
+    }
+}
@@ -1,28 +1,76 @@
-use std::time::Duration;
-
 use bevy::prelude::*;
 use bevy_simple_text_input::{
     TextInput, TextInputInactive, TextInputPlugin, TextInputSettings, TextInputSystem,
     TextInputTextFont, TextInputValidation, TextInputValue,
 };
 
+mod button;
+mod console;
+mod debug_log;
+mod inspector;
+mod key_bindings;
+mod panel;
+mod reflect_value;
+mod scroll;
+#[cfg(feature = "testing")]
+pub mod testing;
+mod theme;
+mod toast;
+mod tooltip;
+mod undo;
+mod value_field;
+mod watch;
+
+pub use button::{ButtonPressed, ButtonShortcut, Disabled, TextButton};
+pub use console::{ConsoleAppExt, ConsoleArgs, ConsoleCommands, ConsolePlugin, ConsoleResult};
+pub use debug_log::{DebugLog, DebugLogFilter, DebugLogView, Level, LogEntry, debug_log_layer};
+pub use inspector::{InspectedEntity, InspectorComponents, InspectorView};
+pub use key_bindings::{
+    ActionInput, Binding, KeyBindings, KeyBindingsAppExt, KeyBindingsView, KeyCapture,
+    action_just_pressed, action_pressed,
+};
+pub use panel::{
+    Draggable, Panel, PanelChrome, PanelLayout, PanelLayoutStore, PanelTitle, Resizable,
+};
+pub use scroll::{MaxHeight, ScrollArea, ScrollExtent};
+pub use theme::Theme;
+pub use toast::{Toast, Toasts};
+pub use tooltip::Tooltip;
+pub use undo::{NoUndo, UndoHistory};
+pub use value_field::{FieldValue, ValueField, ValueFieldPlugin, ValueSubmitted};
+pub use watch::{DebugWatch, DebugWatchView, Plot, Watch, WatchValue};
+
 pub struct PlaygroundUIPlugin;
 
 impl Plugin for PlaygroundUIPlugin {
     fn build(&self, app: &mut App) {
         app.insert_resource(InputUIFocused(None))
+            .init_resource::<Theme>()
             // .insert_resource(DebugLog::new())
             .add_event::<InputUISubmitEvent>()
-            .add_plugins(TextInputPlugin)
-            .add_systems(
-                Update,
-                (
-                    keyboard_handler,
-                    debug_panel_system.run_if(resource_exists::<DebugLog>),
-                    focus.before(TextInputSystem),
-                ),
-            )
-            .add_observer(create_panel)
+            .add_plugins((
+                TextInputPlugin,
+                button::ButtonPlugin,
+                panel::PanelPlugin,
+                scroll::ScrollPlugin,
+                debug_log::DebugLogPlugin,
+                inspector::InspectorPlugin,
+                key_bindings::KeyBindingsPlugin,
+                toast::ToastPlugin,
+                tooltip::TooltipPlugin,
+                undo::UndoPlugin,
+                watch::WatchPlugin,
+            ))
+            .add_plugins((
+                ValueFieldPlugin::<u32>::default(),
+                ValueFieldPlugin::<i32>::default(),
+                ValueFieldPlugin::<i64>::default(),
+                ValueFieldPlugin::<f32>::default(),
+                ValueFieldPlugin::<f64>::default(),
+                ValueFieldPlugin::<bool>::default(),
+                ValueFieldPlugin::<String>::default(),
+            ))
+            .add_systems(Update, (keyboard_handler, focus.before(TextInputSystem)))
             .add_observer(create_text_ui)
             .add_observer(create_header)
             .add_observer(create_input_ui)
@@ -37,76 +85,24 @@ pub struct InputUISubmitEvent;
 #[derive(Resource)]
 pub struct InputUIFocused(Option<Entity>);
 
+/// Run condition for app hotkeys so they don't fire while typing.
+pub fn no_input_focused(focused: Option<Res<InputUIFocused>>) -> bool {
+    focused.is_none_or(|focused| focused.0.is_none())
+}
+
 fn keyboard_handler(
     mut cmd: Commands,
     key_input: Res<ButtonInput<KeyCode>>,
     focused: Res<InputUIFocused>,
 ) {
-    if key_input.just_pressed(KeyCode::Enter) {
-        cmd.trigger_targets(InputUISubmitEvent, focused.0.unwrap());
-    }
-}
-
-#[derive(Component)]
-#[require(Node, PanelTitle)]
-pub struct Panel;
-
-#[derive(Component, Default)]
-pub struct PanelTitle(String);
-
-impl PanelTitle {
-    pub fn new(title: impl Into<String>) -> Self {
-        Self(title.into())
+    if let (true, Some(entity)) = (key_input.just_pressed(KeyCode::Enter), focused.0) {
+        cmd.trigger_targets(InputUISubmitEvent, entity);
     }
 }
 
 #[derive(Component, Clone)]
 pub struct MaxWidth(pub Val);
 
-fn create_panel(
-    trigger: Trigger<OnAdd, Panel>,
-    mut cmd: Commands,
-    title: Query<&PanelTitle>,
-    max_width: Query<&MaxWidth>,
-) {
-    let PanelTitle(title) = title.get(trigger.entity()).unwrap();
-    let MaxWidth(width) = max_width
-        .get(trigger.entity())
-        .unwrap_or(&MaxWidth(Val::Vw(30.)));
-    let title = cmd
-        .spawn((TextUI::new(title), TextLayout {
-            justify: JustifyText::Center,
-            ..default()
-        }))
-        .id();
-    let separator = cmd
-        .spawn((
-            Node {
-                border: UiRect::bottom(Val::Px(1.)),
-                margin: UiRect::bottom(Val::Px(5.)),
-                ..default()
-            },
-            BorderColor(Color::WHITE),
-        ))
-        .id();
-    cmd.entity(trigger.entity())
-        .insert((
-            Panel,
-            Node {
-                max_width: *width,
-                flex_direction: FlexDirection::Column,
-                justify_content: JustifyContent::SpaceBetween,
-                border: UiRect::all(Val::Px(1.)),
-                padding: UiRect::all(Val::Px(5.)),
-                row_gap: Val::Px(3.),
-                ..default()
-            },
-            BackgroundColor(Color::srgb(0.2, 0.2, 0.2)),
-            BorderColor(Color::WHITE),
-        ))
-        .insert_children(0, &[title, separator]);
-}
-
 #[derive(Component, Clone)]
 #[require(Node, Text)]
 pub struct TextUI(String);
@@ -144,6 +140,10 @@ fn create_header(trigger: Trigger<OnAdd, Header>, mut cmd: Commands, header: Que
 #[derive(Component)]
 pub struct InputUI;
 
+/// Keeps the input focused after submitting, e.g. for the console.
+#[derive(Component)]
+pub struct KeepFocusOnSubmit;
+
 #[derive(Component, Default, Clone)]
 pub struct InputUInitialValue(pub String);
 
@@ -153,6 +153,7 @@ fn create_input_ui(
     // input_value: Query<&Parent, With<InputUI>>,
     init_value: Query<&InputUInitialValue>,
     max_width: Query<&MaxWidth>,
+    theme: Res<Theme>,
 ) {
     let InputUInitialValue(value) = init_value.get(trigger.entity()).unwrap();
     let MaxWidth(width) = max_width
@@ -166,8 +167,8 @@ fn create_input_ui(
             padding: UiRect::left(Val::Px(3.)),
             ..default()
         },
-        BorderColor(Color::WHITE),
-        BackgroundColor(Color::srgb(0.3, 0.3, 0.3)),
+        BorderColor(theme.border),
+        BackgroundColor(theme.input_background),
         TextInput,
         TextInputValue(value.clone()),
         TextInputTextFont(TextFont::from_font_size(11.)),
@@ -185,17 +186,23 @@ fn focus(
     query: Query<(Entity, &Interaction), Changed<Interaction>>,
     mut text_input_query: Query<(Entity, &mut TextInputInactive, &mut BackgroundColor)>,
     mut focused: ResMut<InputUIFocused>,
+    theme: Res<Theme>,
 ) {
     for (interaction_entity, interaction) in &query {
         if *interaction == Interaction::Pressed {
+            // Clicking other UI deactivates every input below, the focus has
+            // to follow or keyboard controls stay blocked
+            if !text_input_query.contains(interaction_entity) {
+                focused.0 = None;
+            }
             for (entity, mut inactive, mut background_color) in &mut text_input_query {
                 if entity == interaction_entity {
                     inactive.0 = false;
-                    *background_color = BackgroundColor(Color::srgb(0.4, 0.4, 0.4));
+                    *background_color = BackgroundColor(theme.input_focused);
                     focused.0 = Some(entity);
                 } else if !inactive.0 {
                     inactive.0 = true;
-                    *background_color = BackgroundColor(Color::srgb(0.3, 0.3, 0.3));
+                    *background_color = BackgroundColor(theme.input_background);
                 }
             }
         }
@@ -204,13 +211,14 @@ fn focus(
 
 fn submit_unfocus(
     trigger: Trigger<InputUISubmitEvent>,
-    mut input_ui: Query<(&mut TextInputInactive, &mut BackgroundColor)>,
+    mut input_ui: Query<(&mut TextInputInactive, &mut BackgroundColor), Without<KeepFocusOnSubmit>>,
     mut focused: ResMut<InputUIFocused>,
+    theme: Res<Theme>,
 ) {
     if let Ok((mut inactive, mut background_color)) = input_ui.get_mut(trigger.entity()) {
         focused.0 = None;
         inactive.0 = true;
-        *background_color = BackgroundColor(Color::srgb(0.3, 0.3, 0.3));
+        *background_color = BackgroundColor(theme.input_background);
     }
 }
 
@@ -228,23 +236,41 @@ pub enum InputFieldType {
 
 pub type InputFieldLabel = TextUI;
 
+/// Checks the text of the input on every keystroke, overrides
+/// [`InputFieldType`].
+#[derive(Component, Clone, Copy)]
+pub struct InputFieldFilter(pub fn(&str) -> bool);
+
+/// Unit shown after the input, e.g. "px" or "s".
+#[derive(Component, Clone)]
+pub struct InputFieldUnit(pub String);
+
 fn create_input_field(
     trigger: Trigger<OnAdd, InputField>,
     mut cmd: Commands,
     init_value: Query<&InputUInitialValue>,
     label: Query<&InputFieldLabel>,
     input_type: Query<&InputFieldType>,
+    filter: Query<&InputFieldFilter>,
     name: Query<&Name>,
     width: Query<&MaxWidth>,
+    unit: Query<&InputFieldUnit>,
 ) {
     let init_value = init_value.get(trigger.entity()).unwrap();
     cmd.entity(trigger.entity()).remove::<InputUInitialValue>();
     let label = label.get(trigger.entity()).unwrap();
-    let input_type = input_type.get(trigger.entity()).unwrap();
+    let InputFieldFilter(filter) = filter.get(trigger.entity()).copied().unwrap_or_else(|_| {
+        InputFieldFilter(match input_type.get(trigger.entity()).unwrap() {
+            InputFieldType::String => |_| true,
+            InputFieldType::I32 => |text| text.parse::<i32>().is_ok(),
+            InputFieldType::F32 => |text| text.parse::<f32>().is_ok(),
+        })
+    });
     let name = name.get(trigger.entity()).unwrap();
     let max_width = width
         .get(trigger.entity())
         .unwrap_or(&MaxWidth(Val::Px(45.)));
+    let unit = unit.get(trigger.entity()).ok();
     cmd.entity(trigger.entity())
         .insert((Node {
             flex_direction: FlexDirection::Row,
@@ -254,6 +280,8 @@ fn create_input_field(
         .with_children(|parent| {
             parent.spawn((label.clone(), Node {
                 margin: UiRect::right(Val::Px(3.)),
+                // Keeps the input next to its unit instead of centered
+                flex_grow: if unit.is_some() { 1. } else { 0. },
                 ..default()
             }));
             parent.spawn((
@@ -261,81 +289,17 @@ fn create_input_field(
                 name.clone(),
                 init_value.clone(),
                 max_width.clone(),
-                match input_type {
-                    InputFieldType::String => TextInputValidation(Box::new(|_, _, _| true)),
-                    InputFieldType::I32 => TextInputValidation(Box::new(|text, i, str| {
-                        let mut text = text.clone();
-                        text.insert_str(i, str);
-                        text.parse::<i32>().is_ok()
-                    })),
-                    InputFieldType::F32 => TextInputValidation(Box::new(|text, i, str| {
-                        let mut text = text.clone();
-                        text.insert_str(i, str);
-                        text.parse::<f32>().is_ok()
-                    })),
-                },
+                TextInputValidation(Box::new(move |text, i, str| {
+                    let mut text = text.clone();
+                    text.insert_str(i, str);
+                    filter(&text)
+                })),
             ));
+            if let Some(InputFieldUnit(unit)) = unit {
+                parent.spawn((TextUI::new(unit.clone()), Node {
+                    margin: UiRect::left(Val::Px(2.)),
+                    ..default()
+                }));
+            }
         });
 }
-
-#[derive(Resource)]
-pub struct DebugLog {
-    content: Vec<String>,
-    timer: Timer,
-    ready_push: bool,
-}
-
-#[derive(Component)]
-pub struct DebugPanelText;
-
-impl DebugLog {
-    pub fn new() -> Self {
-        Self {
-            content: vec![String::from(" "); 5],
-            timer: Timer::new(Duration::from_secs_f32(0.3), TimerMode::Repeating),
-            ready_push: false,
-        }
-    }
-
-    pub fn push(&mut self, content: impl Into<String>) {
-        self.content.remove(0);
-        self.content.push(content.into());
-    }
-
-    #[allow(unused)]
-    pub fn push_timed(&mut self, content: impl Into<String>) {
-        if self.ready_push {
-            self.push(content);
-            self.ready_push = false;
-        }
-    }
-
-    fn get_content(&self) -> String {
-        self.content.join("\n")
-    }
-
-    fn tick(&mut self, time: &Time) {
-        if self.timer.tick(time.delta()).just_finished() && !self.ready_push {
-            self.ready_push = true;
-        }
-    }
-}
-
-impl Default for DebugLog {
-    fn default() -> Self {
-        Self::new()
-    }
-}
-
-fn debug_panel_system(
-    time: Res<Time>,
-    mut debug_log: ResMut<DebugLog>,
-    mut debug_text: Query<&mut Text, With<DebugPanelText>>,
-) {
-    debug_log.tick(&time);
-    if debug_log.is_changed() {
-        for mut debug_text in debug_text.iter_mut() {
-            debug_text.0 = debug_log.get_content();
-        }
-    }
-}
@@ -0,0 +1,353 @@
+use std::{collections::HashMap, fs, path::PathBuf, time::Duration};
+
+use bevy::{prelude::*, time::common_conditions::on_timer};
+use serde::{Deserialize, Serialize};
+
+use crate::{MaxWidth, TextUI, Theme};
+
+pub struct PanelPlugin;
+
+impl Plugin for PanelPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<PanelStack>()
+            .add_systems(
+                Update,
+                (
+                    apply_panel_layout,
+                    (
+                        store_panel_layout,
+                        save_panel_layout
+                            .run_if(on_timer(Duration::from_secs(1)).or(on_event::<AppExit>)),
+                    )
+                        .chain()
+                        .run_if(resource_exists::<PanelLayoutStore>),
+                ),
+            )
+            .add_observer(create_panel);
+    }
+}
+
+#[derive(Component)]
+#[require(Node, PanelTitle, PanelLayout)]
+pub struct Panel;
+
+#[derive(Component, Default)]
+pub struct PanelTitle(String);
+
+impl PanelTitle {
+    pub fn new(title: impl Into<String>) -> Self {
+        Self(title.into())
+    }
+}
+
+/// Lets the panel be moved around by dragging its title.
+#[derive(Component)]
+pub struct Draggable;
+
+/// Adds a handle in the bottom right corner of the panel to resize it.
+#[derive(Component)]
+pub struct Resizable;
+
+/// Runtime layout of a panel. `offset` is relative to where the panel would
+/// be placed by its parent, `size` is `None` until the panel gets resized.
+#[derive(Component, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub struct PanelLayout {
+    pub offset: Vec2,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub size: Option<Vec2>,
+    #[serde(default)]
+    pub collapsed: bool,
+}
+
+/// Title, separator and resize handle of a panel, these stay visible when
+/// the panel is collapsed.
+#[derive(Component)]
+pub struct PanelChrome;
+
+#[derive(Component, Default)]
+struct PanelTitleBar {
+    title: String,
+    dragged: bool,
+}
+
+#[derive(Component)]
+struct PanelResizeHandle;
+
+#[derive(Resource, Default)]
+struct PanelStack(i32);
+
+const PANEL_MIN_SIZE: Vec2 = Vec2::new(60., 30.);
+
+/// Persists [`PanelLayout`] of every panel between runs. Panels are keyed by
+/// their `Name`, or by their title if they don't have one.
+#[derive(Resource)]
+pub struct PanelLayoutStore {
+    path: PathBuf,
+    layouts: HashMap<String, PanelLayout>,
+    dirty: bool,
+}
+
+impl PanelLayoutStore {
+    pub fn load(path: impl Into<PathBuf>) -> Self {
+        let path = path.into();
+        let layouts = fs::read_to_string(&path)
+            .ok()
+            .and_then(|content| toml::from_str(&content).ok())
+            .unwrap_or_default();
+        Self {
+            path,
+            layouts,
+            dirty: false,
+        }
+    }
+
+    pub fn get(&self, key: &str) -> Option<&PanelLayout> {
+        self.layouts.get(key)
+    }
+
+    fn save(&mut self) {
+        if !self.dirty {
+            return;
+        }
+        self.dirty = false;
+        match toml::to_string(&self.layouts) {
+            Ok(content) => {
+                if let Err(err) = fs::write(&self.path, content) {
+                    warn!("Failed to save panel layout to {:?}: {}", self.path, err);
+                }
+            }
+            Err(err) => warn!("Failed to serialize panel layout: {}", err),
+        }
+    }
+}
+
+fn panel_key(title: &PanelTitle, name: Option<&Name>) -> String {
+    name.map(|name| name.to_string())
+        .unwrap_or_else(|| title.0.clone())
+}
+
+fn title_text(title: &str, collapsed: bool) -> String {
+    format!("[{}] {}", if collapsed { "+" } else { "-" }, title)
+}
+
+fn create_panel(
+    trigger: Trigger<OnAdd, Panel>,
+    mut cmd: Commands,
+    panel: Query<(&PanelTitle, Option<&Name>, Has<Resizable>)>,
+    max_width: Query<&MaxWidth>,
+    store: Option<Res<PanelLayoutStore>>,
+    theme: Res<Theme>,
+) {
+    let (title, name, resizable) = panel.get(trigger.entity()).unwrap();
+    let MaxWidth(width) = max_width
+        .get(trigger.entity())
+        .unwrap_or(&MaxWidth(Val::Vw(30.)));
+    let title_bar = cmd
+        .spawn((
+            TextUI::new(title_text(&title.0, false)),
+            TextLayout {
+                justify: JustifyText::Center,
+                ..default()
+            },
+            PanelChrome,
+            PanelTitleBar {
+                title: title.0.clone(),
+                dragged: false,
+            },
+        ))
+        .observe(toggle_collapse)
+        .observe(drag_panel)
+        .observe(end_drag_panel)
+        .id();
+    let separator = cmd
+        .spawn((
+            Node {
+                border: UiRect::bottom(Val::Px(1.)),
+                margin: UiRect::bottom(Val::Px(5.)),
+                ..default()
+            },
+            BorderColor(theme.border),
+            PanelChrome,
+        ))
+        .id();
+    cmd.entity(trigger.entity())
+        .insert((
+            Panel,
+            Node {
+                max_width: *width,
+                flex_direction: FlexDirection::Column,
+                justify_content: JustifyContent::SpaceBetween,
+                border: UiRect::all(Val::Px(1.)),
+                padding: UiRect::all(Val::Px(5.)),
+                row_gap: Val::Px(3.),
+                ..default()
+            },
+            BackgroundColor(theme.panel_background),
+            BorderColor(theme.border),
+        ))
+        .insert_children(0, &[title_bar, separator])
+        .observe(raise_panel);
+
+    if resizable {
+        cmd.entity(trigger.entity()).with_children(|parent| {
+            parent
+                .spawn((
+                    Node {
+                        position_type: PositionType::Absolute,
+                        right: Val::Px(0.),
+                        bottom: Val::Px(0.),
+                        width: Val::Px(7.),
+                        height: Val::Px(7.),
+                        ..default()
+                    },
+                    BackgroundColor(theme.border),
+                    PanelChrome,
+                    PanelResizeHandle,
+                ))
+                .observe(resize_panel);
+        });
+    }
+
+    if let Some(layout) = store.and_then(|store| store.get(&panel_key(title, name)).copied()) {
+        cmd.entity(trigger.entity()).insert(layout);
+    }
+}
+
+fn toggle_collapse(
+    trigger: Trigger<Pointer<Click>>,
+    title_bar: Query<(&PanelTitleBar, &Parent)>,
+    mut layout: Query<&mut PanelLayout>,
+) {
+    if trigger.button != PointerButton::Primary {
+        return;
+    }
+    let Ok((title_bar, parent)) = title_bar.get(trigger.entity()) else {
+        return;
+    };
+    // Releasing a drag over the title also counts as a click
+    if title_bar.dragged {
+        return;
+    }
+    if let Ok(mut layout) = layout.get_mut(parent.get()) {
+        layout.collapsed = !layout.collapsed;
+    }
+}
+
+fn drag_panel(
+    trigger: Trigger<Pointer<Drag>>,
+    mut title_bar: Query<(&mut PanelTitleBar, &Parent)>,
+    mut layout: Query<&mut PanelLayout, With<Draggable>>,
+    ui_scale: Res<UiScale>,
+) {
+    if trigger.button != PointerButton::Primary {
+        return;
+    }
+    let Ok((mut title_bar, parent)) = title_bar.get_mut(trigger.entity()) else {
+        return;
+    };
+    if trigger.distance.length() > 3. {
+        title_bar.dragged = true;
+    }
+    if let Ok(mut layout) = layout.get_mut(parent.get()) {
+        layout.offset += trigger.delta / ui_scale.0;
+    }
+}
+
+fn end_drag_panel(trigger: Trigger<Pointer<DragEnd>>, mut title_bar: Query<&mut PanelTitleBar>) {
+    if let Ok(mut title_bar) = title_bar.get_mut(trigger.entity()) {
+        title_bar.dragged = false;
+    }
+}
+
+fn resize_panel(
+    trigger: Trigger<Pointer<Drag>>,
+    handle: Query<&Parent, With<PanelResizeHandle>>,
+    mut panel: Query<(&mut PanelLayout, &ComputedNode)>,
+    ui_scale: Res<UiScale>,
+) {
+    if trigger.button != PointerButton::Primary {
+        return;
+    }
+    let Ok(parent) = handle.get(trigger.entity()) else {
+        return;
+    };
+    let Ok((mut layout, computed)) = panel.get_mut(parent.get()) else {
+        return;
+    };
+    let size = layout
+        .size
+        .unwrap_or(computed.size() * computed.inverse_scale_factor());
+    layout.size = Some((size + trigger.delta / ui_scale.0).max(PANEL_MIN_SIZE));
+}
+
+fn raise_panel(
+    trigger: Trigger<Pointer<Down>>,
+    mut cmd: Commands,
+    panel: Query<(), With<Draggable>>,
+    mut stack: ResMut<PanelStack>,
+) {
+    // Only the panel being observed is raised, the event still bubbles up to
+    // the parent panels so they get raised among their own siblings.
+    if panel.contains(trigger.entity()) {
+        stack.0 += 1;
+        cmd.entity(trigger.entity()).insert(ZIndex(stack.0));
+    }
+}
+
+fn apply_panel_layout(
+    panels: Query<(Entity, &PanelLayout, &Children), Changed<PanelLayout>>,
+    mut nodes: Query<&mut Node>,
+    chrome: Query<(), With<PanelChrome>>,
+    mut title_bar: Query<(&PanelTitleBar, &mut Text)>,
+) {
+    for (entity, layout, children) in panels.iter() {
+        if let Ok(mut node) = nodes.get_mut(entity) {
+            node.left = Val::Px(layout.offset.x);
+            node.top = Val::Px(layout.offset.y);
+            if let Some(size) = layout.size {
+                node.width = Val::Px(size.x);
+                node.max_width = Val::Px(size.x);
+                node.height = if layout.collapsed {
+                    Val::Auto
+                } else {
+                    Val::Px(size.y)
+                };
+            }
+        }
+        for child in children.iter() {
+            if let Ok((title_bar, mut text)) = title_bar.get_mut(*child) {
+                let title = title_text(&title_bar.title, layout.collapsed);
+                if text.0 != title {
+                    text.0 = title;
+                }
+            }
+            if chrome.contains(*child) {
+                continue;
+            }
+            if let Ok(mut node) = nodes.get_mut(*child) {
+                node.display = if layout.collapsed {
+                    Display::None
+                } else {
+                    Display::Flex
+                };
+            }
+        }
+    }
+}
+
+fn store_panel_layout(
+    panels: Query<(&PanelLayout, &PanelTitle, Option<&Name>), Changed<PanelLayout>>,
+    mut store: ResMut<PanelLayoutStore>,
+) {
+    for (layout, title, name) in panels.iter() {
+        let key = panel_key(title, name);
+        if store.layouts.get(&key) != Some(layout) {
+            store.layouts.insert(key, *layout);
+            store.dirty = true;
+        }
+    }
+}
+
+fn save_panel_layout(mut store: ResMut<PanelLayoutStore>) {
+    store.save();
+}
@@ -0,0 +1,92 @@
+use bevy::reflect::{
+    DynamicEnum, DynamicTypePath, DynamicVariant, PartialReflect, ReflectMut, ReflectRef,
+};
+
+/// Parses `value` into the reflected field, supports numbers, `bool`,
+/// `String` and unit enum variants.
+pub(crate) fn set_from_str(field: &mut dyn PartialReflect, value: &str) -> Result<(), String> {
+    macro_rules! parse_into {
+        ($($ty:ty),*) => {
+            $(
+                if let Some(field) = field.try_downcast_mut::<$ty>() {
+                    *field = value
+                        .parse::<$ty>()
+                        .map_err(|err| format!("invalid {} '{}': {}", stringify!($ty), value, err))?;
+                    return Ok(());
+                }
+            )*
+        };
+    }
+
+    parse_into!(
+        f32, f64, i8, i16, i32, i64, u8, u16, u32, u64, usize, isize, bool
+    );
+
+    if let Some(field) = field.try_downcast_mut::<String>() {
+        *field = value.to_string();
+        return Ok(());
+    }
+
+    if let ReflectMut::Enum(field) = field.reflect_mut() {
+        let variant = field
+            .get_represented_enum_info()
+            .and_then(|info| {
+                info.variant_names()
+                    .iter()
+                    .find(|name| name.eq_ignore_ascii_case(value))
+            })
+            .ok_or_else(|| format!("unknown variant '{}'", value))?;
+        return field
+            .try_apply(&DynamicEnum::new(*variant, DynamicVariant::Unit))
+            .map_err(|err| err.to_string());
+    }
+
+    Err(format!(
+        "can't set {} from text",
+        field.reflect_short_type_path()
+    ))
+}
+
+pub(crate) fn format_reflect(value: &dyn PartialReflect) -> String {
+    macro_rules! format_number {
+        ($($ty:ty),*) => {
+            $(
+                if let Some(value) = value.try_downcast_ref::<$ty>() {
+                    return value.to_string();
+                }
+            )*
+        };
+    }
+
+    format_number!(
+        f32, f64, i8, i16, i32, i64, u8, u16, u32, u64, usize, isize, bool, String
+    );
+
+    format!("{:?}", value)
+}
+
+/// Flattens structs and tuple structs into `(path, value)` pairs of their
+/// leaf fields, e.g. `.translation.x`. The path is empty for leaf values.
+pub(crate) fn leaf_fields(
+    value: &dyn PartialReflect,
+    path: String,
+    out: &mut Vec<(String, String)>,
+) {
+    match value.reflect_ref() {
+        ReflectRef::Struct(value) => {
+            for (i, field) in value.iter_fields().enumerate() {
+                leaf_fields(
+                    field,
+                    format!("{}.{}", path, value.name_at(i).unwrap()),
+                    out,
+                );
+            }
+        }
+        ReflectRef::TupleStruct(value) => {
+            for (i, field) in value.iter_fields().enumerate() {
+                leaf_fields(field, format!("{}.{}", path, i), out);
+            }
+        }
+        _ => out.push((path, format_reflect(value))),
+    }
+}
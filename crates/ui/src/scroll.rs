@@ -0,0 +1,239 @@
+use bevy::{
+    input::mouse::{MouseScrollUnit, MouseWheel},
+    picking::focus::HoverMap,
+    prelude::*,
+    utils::HashSet,
+};
+
+pub struct ScrollPlugin;
+
+impl Plugin for ScrollPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            Update,
+            (
+                measure_scroll_area,
+                scroll_wheel,
+                update_scrollbar,
+                cull_scroll_rows,
+            )
+                .chain(),
+        )
+        .add_observer(create_scroll_area);
+    }
+}
+
+/// Vertically scrollable container, children taller than [`MaxHeight`] are
+/// clipped and can be scrolled with the mouse wheel or by dragging the bar.
+#[derive(Component)]
+#[require(Node, ScrollExtent)]
+pub struct ScrollArea;
+
+#[derive(Component, Clone)]
+pub struct MaxHeight(pub Val);
+
+/// Visible and total height of a [`ScrollArea`] content in logical pixels.
+#[derive(Component, Default, Clone, Copy, PartialEq)]
+pub struct ScrollExtent {
+    pub view: f32,
+    pub content: f32,
+}
+
+impl ScrollExtent {
+    pub fn max_offset(&self) -> f32 {
+        (self.content - self.view).max(0.)
+    }
+}
+
+#[derive(Component)]
+struct ScrollbarThumb;
+
+const LINE_HEIGHT: f32 = 20.;
+const SCROLLBAR_WIDTH: f32 = 4.;
+const SCROLLBAR_MIN_HEIGHT: f32 = 10.;
+
+fn create_scroll_area(
+    trigger: Trigger<OnAdd, ScrollArea>,
+    mut cmd: Commands,
+    max_height: Query<&MaxHeight>,
+) {
+    let MaxHeight(height) = max_height
+        .get(trigger.entity())
+        .unwrap_or(&MaxHeight(Val::Px(200.)));
+    cmd.entity(trigger.entity())
+        .insert(Node {
+            max_height: *height,
+            flex_direction: FlexDirection::Column,
+            overflow: Overflow::scroll_y(),
+            padding: UiRect::right(Val::Px(SCROLLBAR_WIDTH + 2.)),
+            row_gap: Val::Px(3.),
+            ..default()
+        })
+        .with_children(|parent| {
+            parent
+                .spawn((
+                    ScrollbarThumb,
+                    Node {
+                        display: Display::None,
+                        position_type: PositionType::Absolute,
+                        right: Val::Px(0.),
+                        width: Val::Px(SCROLLBAR_WIDTH),
+                        ..default()
+                    },
+                    BackgroundColor(Color::srgb(0.6, 0.6, 0.6)),
+                ))
+                .observe(drag_scrollbar);
+        });
+}
+
+fn measure_scroll_area(
+    mut area: Query<
+        (
+            &mut ScrollExtent,
+            &ComputedNode,
+            &GlobalTransform,
+            &ScrollPosition,
+            &Children,
+        ),
+        With<ScrollArea>,
+    >,
+    rows: Query<(&Node, &ComputedNode, &GlobalTransform), Without<ScrollbarThumb>>,
+) {
+    for (mut extent, computed, transform, scroll, children) in area.iter_mut() {
+        let scale = computed.inverse_scale_factor();
+        let top = transform.translation().y - computed.size().y / 2.;
+        let bottom = children
+            .iter()
+            .filter_map(|child| rows.get(*child).ok())
+            .filter(|(node, ..)| node.display != Display::None)
+            .map(|(_, row, row_transform)| row_transform.translation().y + row.size().y / 2.)
+            .fold(top, f32::max);
+        extent.set_if_neq(ScrollExtent {
+            view: computed.size().y * scale,
+            content: (bottom - top + computed.padding().bottom) * scale + scroll.offset_y,
+        });
+    }
+}
+
+fn scroll_wheel(
+    mut wheel: EventReader<MouseWheel>,
+    hover_map: Res<HoverMap>,
+    parent: Query<&Parent>,
+    mut area: Query<(&mut ScrollPosition, &ScrollExtent), With<ScrollArea>>,
+) {
+    for event in wheel.read() {
+        let dy = match event.unit {
+            MouseScrollUnit::Line => event.y * LINE_HEIGHT,
+            MouseScrollUnit::Pixel => event.y,
+        };
+        // Only the innermost area under each pointer gets scrolled
+        let hovered = hover_map
+            .values()
+            .flat_map(|pointer_map| pointer_map.keys())
+            .filter_map(|entity| {
+                std::iter::once(*entity)
+                    .chain(parent.iter_ancestors(*entity))
+                    .find(|entity| area.contains(*entity))
+            })
+            .collect::<HashSet<_>>();
+        for entity in hovered {
+            let (mut scroll, extent) = area.get_mut(entity).unwrap();
+            scroll.offset_y = (scroll.offset_y - dy).clamp(0., extent.max_offset());
+        }
+    }
+}
+
+fn drag_scrollbar(
+    trigger: Trigger<Pointer<Drag>>,
+    thumb: Query<&Parent, With<ScrollbarThumb>>,
+    mut area: Query<(&mut ScrollPosition, &ScrollExtent)>,
+    ui_scale: Res<UiScale>,
+) {
+    let Ok(parent) = thumb.get(trigger.entity()) else {
+        return;
+    };
+    let Ok((mut scroll, extent)) = area.get_mut(parent.get()) else {
+        return;
+    };
+    if extent.view <= 0. {
+        return;
+    }
+    let dy = trigger.delta.y / ui_scale.0 * extent.content / extent.view;
+    scroll.offset_y = (scroll.offset_y + dy).clamp(0., extent.max_offset());
+}
+
+fn update_scrollbar(
+    area: Query<(&ScrollExtent, &ScrollPosition, &Children), With<ScrollArea>>,
+    mut thumb: Query<&mut Node, With<ScrollbarThumb>>,
+) {
+    for (extent, scroll, children) in area.iter() {
+        let mut thumbs = thumb.iter_many_mut(children);
+        while let Some(mut node) = thumbs.fetch_next() {
+            if extent.max_offset() <= 0. {
+                if node.display != Display::None {
+                    node.display = Display::None;
+                }
+                continue;
+            }
+            let height = (extent.view * extent.view / extent.content).max(SCROLLBAR_MIN_HEIGHT);
+            let progress = scroll.offset_y / extent.max_offset();
+            // The thumb is a child of the scrolled node, so it has to follow
+            // the scroll offset to stay in view.
+            let top = scroll.offset_y + progress * (extent.view - height);
+            if node.display != Display::Flex || node.top != Val::Px(top) {
+                node.display = Display::Flex;
+                node.top = Val::Px(top);
+                node.height = Val::Px(height);
+            }
+        }
+    }
+}
+
+/// Row hidden by [`cull_scroll_rows`] because it's out of view, holding the
+/// visibility to restore when it scrolls back in.
+#[derive(Component)]
+struct Culled(Visibility);
+
+fn cull_scroll_rows(
+    mut cmd: Commands,
+    area: Query<(&ComputedNode, &GlobalTransform, &Children), With<ScrollArea>>,
+    mut rows: Query<
+        (
+            Entity,
+            &ComputedNode,
+            &GlobalTransform,
+            &mut Visibility,
+            Option<&Culled>,
+        ),
+        Without<ScrollbarThumb>,
+    >,
+) {
+    for (computed, transform, children) in area.iter() {
+        let view = computed.size().y;
+        let top = transform.translation().y - view / 2.;
+        // Rows are positioned by the layout after this runs, keep a margin
+        // around the view so they don't pop in one frame late when scrolling.
+        let (min, max) = (top - view, top + view * 2.);
+        let mut rows = rows.iter_many_mut(children);
+        while let Some((id, row, row_transform, mut visibility, culled)) = rows.fetch_next() {
+            let y = row_transform.translation().y;
+            let half = row.size().y / 2.;
+            let out_of_view = y + half < min || y - half > max;
+            match culled {
+                // Rows hidden by someone else are left alone
+                None if out_of_view && *visibility != Visibility::Hidden => {
+                    cmd.entity(id).insert(Culled(*visibility));
+                    *visibility = Visibility::Hidden;
+                }
+                Some(Culled(previous)) if !out_of_view => {
+                    // Unless the visibility was changed while culled
+                    if *visibility == Visibility::Hidden {
+                        *visibility = *previous;
+                    }
+                    cmd.entity(id).remove::<Culled>();
+                }
+                _ => {}
+            }
+        }
+    }
+}
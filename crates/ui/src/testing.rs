@@ -0,0 +1,255 @@
+use std::marker::PhantomData;
+
+use bevy::{
+    input::{
+        ButtonState, InputPlugin,
+        keyboard::{Key, KeyboardInput, NativeKey, NativeKeyCode},
+        mouse::MouseButtonInput,
+    },
+    picking::{
+        backend::HitData,
+        focus::HoverMap,
+        pointer::{Location, PointerButton, PointerId},
+    },
+    prelude::*,
+    render::camera::NormalizedRenderTarget,
+};
+use bevy_simple_text_input::{TextInputInactive, TextInputValue};
+
+use crate::{InputUI, InputUIFocused, PlaygroundUIPlugin};
+
+/// Headless app with the playground UI for driving widgets from tests.
+/// Input goes through the same events winit would send, clicks go straight
+/// to the clicked node since there's no layout or picking backend.
+///
+/// ```ignore
+/// let mut app = UiTestApp::new();
+/// let input = app.spawn_input_field("speed", "1.0");
+/// app.click(input);
+/// app.type_text("5");
+/// app.press_key(KeyCode::Enter);
+/// assert_eq!(app.value(input), "1.05");
+/// ```
+pub struct UiTestApp {
+    pub app: App,
+}
+
+impl UiTestApp {
+    pub fn new() -> Self {
+        let mut app = App::new();
+        app.add_plugins((MinimalPlugins, InputPlugin, PlaygroundUIPlugin))
+            .init_resource::<HoverMap>()
+            .init_resource::<UiScale>()
+            .init_resource::<Assets<Image>>();
+        // Startup systems spawn the widget roots
+        app.update();
+        Self { app }
+    }
+
+    pub fn world(&self) -> &World {
+        self.app.world()
+    }
+
+    pub fn world_mut(&mut self) -> &mut World {
+        self.app.world_mut()
+    }
+
+    pub fn update(&mut self) {
+        self.app.update();
+    }
+
+    /// Spawns the bundle and runs a frame so its widget gets built.
+    pub fn spawn(&mut self, bundle: impl Bundle) -> Entity {
+        let entity = self.world_mut().spawn(bundle).id();
+        self.update();
+        entity
+    }
+
+    /// Spawns a string [`InputField`](crate::InputField) and returns its
+    /// input.
+    pub fn spawn_input_field(&mut self, name: &str, value: &str) -> Entity {
+        self.spawn((
+            crate::InputField,
+            crate::InputFieldLabel::new(name),
+            crate::InputFieldType::String,
+            crate::InputUInitialValue(value.into()),
+            Name::new(name.to_string()),
+        ));
+        self.input(name)
+    }
+
+    /// Input created for the field with the given name.
+    pub fn input(&mut self, name: &str) -> Entity {
+        let mut query = self
+            .world_mut()
+            .query_filtered::<(Entity, &Name), With<InputUI>>();
+        query
+            .iter(self.world())
+            .find(|(_, input_name)| input_name.as_str() == name)
+            .map(|(entity, _)| entity)
+            .unwrap_or_else(|| panic!("No input named {}", name))
+    }
+
+    pub fn value(&self, input: Entity) -> String {
+        self.world().get::<TextInputValue>(input).unwrap().0.clone()
+    }
+
+    pub fn set_value(&mut self, input: Entity, value: &str) {
+        self.world_mut().get_mut::<TextInputValue>(input).unwrap().0 = value.into();
+    }
+
+    pub fn text(&self, entity: Entity) -> String {
+        self.world().get::<Text>(entity).unwrap().0.clone()
+    }
+
+    pub fn focused(&self) -> Option<Entity> {
+        self.world().resource::<InputUIFocused>().0
+    }
+
+    pub fn is_active(&self, input: Entity) -> bool {
+        !self.world().get::<TextInputInactive>(input).unwrap().0
+    }
+
+    /// Presses and releases the left mouse button over the node.
+    pub fn click(&mut self, entity: Entity) {
+        self.mouse_button(MouseButton::Left, ButtonState::Pressed);
+        self.world_mut()
+            .entity_mut(entity)
+            .insert(Interaction::Pressed);
+        self.trigger_pointer(entity, Down {
+            button: PointerButton::Primary,
+            hit: Self::hit(),
+        });
+        self.update();
+
+        self.mouse_button(MouseButton::Left, ButtonState::Released);
+        self.world_mut()
+            .entity_mut(entity)
+            .insert(Interaction::None);
+        self.trigger_pointer(entity, Click {
+            button: PointerButton::Primary,
+            hit: Self::hit(),
+            duration: default(),
+        });
+        self.update();
+    }
+
+    /// Types the text into whatever input is active, one key per character.
+    pub fn type_text(&mut self, text: &str) {
+        for c in text.chars() {
+            let key = Key::Character(c.to_string().into());
+            self.keyboard(
+                KeyCode::Unidentified(NativeKeyCode::Unidentified),
+                key.clone(),
+                ButtonState::Pressed,
+            );
+            self.keyboard(
+                KeyCode::Unidentified(NativeKeyCode::Unidentified),
+                key,
+                ButtonState::Released,
+            );
+        }
+        self.update();
+    }
+
+    /// Presses the key for one frame and releases it.
+    pub fn press_key(&mut self, key_code: KeyCode) {
+        self.hold_key(key_code);
+        self.release_key(key_code);
+    }
+
+    pub fn hold_key(&mut self, key_code: KeyCode) {
+        self.keyboard(key_code, logical_key(key_code), ButtonState::Pressed);
+        self.update();
+    }
+
+    pub fn release_key(&mut self, key_code: KeyCode) {
+        self.keyboard(key_code, logical_key(key_code), ButtonState::Released);
+        self.update();
+    }
+
+    /// Starts recording the entities `E` gets triggered on, see
+    /// [`UiTestApp::triggered`].
+    pub fn record<E: Event>(&mut self) {
+        if self.world().contains_resource::<Triggered<E>>() {
+            return;
+        }
+        self.world_mut()
+            .insert_resource(Triggered::<E>(vec![], PhantomData));
+        self.world_mut().add_observer(
+            |trigger: Trigger<E>, mut triggered: ResMut<Triggered<E>>| {
+                triggered.0.push(trigger.entity());
+            },
+        );
+    }
+
+    /// Targets of every `E` triggered since [`UiTestApp::record`], in order.
+    pub fn triggered<E: Event>(&self) -> &[Entity] {
+        &self
+            .world()
+            .get_resource::<Triggered<E>>()
+            .expect("Event isn't being recorded")
+            .0
+    }
+
+    fn keyboard(&mut self, key_code: KeyCode, logical_key: Key, state: ButtonState) {
+        self.world_mut().send_event(KeyboardInput {
+            key_code,
+            logical_key,
+            state,
+            repeat: false,
+            window: Entity::PLACEHOLDER,
+        });
+    }
+
+    fn mouse_button(&mut self, button: MouseButton, state: ButtonState) {
+        self.world_mut().send_event(MouseButtonInput {
+            button,
+            state,
+            window: Entity::PLACEHOLDER,
+        });
+    }
+
+    fn trigger_pointer<E: std::fmt::Debug + Clone + Reflect>(&mut self, entity: Entity, event: E) {
+        let location = Location {
+            target: NormalizedRenderTarget::Image(default()),
+            position: Vec2::ZERO,
+        };
+        self.world_mut().trigger_targets(
+            Pointer::new(entity, PointerId::Mouse, location, event),
+            entity,
+        );
+        self.world_mut().flush();
+    }
+
+    fn hit() -> HitData {
+        HitData::new(Entity::PLACEHOLDER, 0., None, None)
+    }
+}
+
+impl Default for UiTestApp {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[derive(Resource)]
+struct Triggered<E: Event>(Vec<Entity>, PhantomData<E>);
+
+fn logical_key(key_code: KeyCode) -> Key {
+    match key_code {
+        KeyCode::Enter | KeyCode::NumpadEnter => Key::Enter,
+        KeyCode::Backspace => Key::Backspace,
+        KeyCode::Delete => Key::Delete,
+        KeyCode::Escape => Key::Escape,
+        KeyCode::Tab => Key::Tab,
+        KeyCode::Space => Key::Space,
+        KeyCode::Home => Key::Home,
+        KeyCode::End => Key::End,
+        KeyCode::ArrowLeft => Key::ArrowLeft,
+        KeyCode::ArrowRight => Key::ArrowRight,
+        KeyCode::ArrowUp => Key::ArrowUp,
+        KeyCode::ArrowDown => Key::ArrowDown,
+        _ => Key::Unidentified(NativeKey::Unidentified),
+    }
+}
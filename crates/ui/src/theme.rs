@@ -0,0 +1,33 @@
+use bevy::prelude::*;
+
+/// Colors shared by the playground widgets.
+#[derive(Resource, Clone)]
+pub struct Theme {
+    pub border: Color,
+    pub panel_background: Color,
+    pub input_background: Color,
+    pub input_focused: Color,
+    pub button: Color,
+    pub button_hovered: Color,
+    pub button_pressed: Color,
+    pub button_disabled: Color,
+    pub text: Color,
+    pub text_disabled: Color,
+}
+
+impl Default for Theme {
+    fn default() -> Self {
+        Self {
+            border: Color::WHITE,
+            panel_background: Color::srgb(0.2, 0.2, 0.2),
+            input_background: Color::srgb(0.3, 0.3, 0.3),
+            input_focused: Color::srgb(0.4, 0.4, 0.4),
+            button: Color::srgb(0.3, 0.3, 0.3),
+            button_hovered: Color::srgb(0.4, 0.4, 0.4),
+            button_pressed: Color::srgb(0.55, 0.55, 0.55),
+            button_disabled: Color::srgb(0.15, 0.15, 0.15),
+            text: Color::WHITE,
+            text_disabled: Color::srgb(0.5, 0.5, 0.5),
+        }
+    }
+}
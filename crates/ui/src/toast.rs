@@ -0,0 +1,200 @@
+use std::time::Duration;
+
+use bevy::prelude::*;
+
+use crate::{Level, LogEntry, TextUI, Theme};
+
+pub struct ToastPlugin;
+
+impl Plugin for ToastPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<Toasts>()
+            .add_systems(Startup, spawn_toast_stack)
+            .add_systems(Update, (expire_toasts, update_toast_stack).chain());
+    }
+}
+
+pub struct Toast {
+    id: u64,
+    pub level: Level,
+    pub message: String,
+    timer: Timer,
+}
+
+/// Short notifications stacked in the bottom right corner of the screen,
+/// e.g. `toasts.push(Level::Warn, "...")`. They expire after `duration` or
+/// when clicked.
+#[derive(Resource)]
+pub struct Toasts {
+    toasts: Vec<Toast>,
+    next_id: u64,
+    pub duration: Duration,
+    pub max: usize,
+    /// Log events at or above this level are shown as toasts too, this needs
+    /// [`debug_log_layer`](crate::debug_log_layer) to be set up.
+    pub log_level: Option<Level>,
+}
+
+impl Toasts {
+    pub fn new() -> Self {
+        Self {
+            toasts: vec![],
+            next_id: 0,
+            duration: Duration::from_secs(4),
+            max: 5,
+            log_level: None,
+        }
+    }
+
+    pub fn with_duration(mut self, duration: Duration) -> Self {
+        self.duration = duration;
+        self
+    }
+
+    pub fn with_log_level(mut self, level: Level) -> Self {
+        self.log_level = Some(level);
+        self
+    }
+
+    pub fn push(&mut self, level: Level, message: impl Into<String>) -> u64 {
+        self.push_with_duration(level, message, self.duration)
+    }
+
+    pub fn push_with_duration(
+        &mut self,
+        level: Level,
+        message: impl Into<String>,
+        duration: Duration,
+    ) -> u64 {
+        let id = self.next_id;
+        self.next_id += 1;
+        if self.toasts.len() >= self.max.max(1) {
+            self.toasts.remove(0);
+        }
+        self.toasts.push(Toast {
+            id,
+            level,
+            message: message.into(),
+            timer: Timer::new(duration, TimerMode::Once),
+        });
+        id
+    }
+
+    pub fn dismiss(&mut self, id: u64) {
+        self.toasts.retain(|toast| toast.id != id);
+    }
+
+    pub fn clear(&mut self) {
+        self.toasts.clear();
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &Toast> {
+        self.toasts.iter()
+    }
+
+    pub(crate) fn push_log_entry(&mut self, entry: &LogEntry) {
+        if self.log_level.is_some_and(|level| entry.level >= level) {
+            self.push(entry.level, entry.message.clone());
+        }
+    }
+}
+
+impl Default for Toasts {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[derive(Component)]
+struct ToastStack;
+
+#[derive(Component)]
+struct ToastRow(u64);
+
+fn spawn_toast_stack(mut cmd: Commands) {
+    cmd.spawn((
+        ToastStack,
+        Node {
+            position_type: PositionType::Absolute,
+            right: Val::Px(10.),
+            bottom: Val::Px(10.),
+            max_width: Val::Px(300.),
+            flex_direction: FlexDirection::Column,
+            align_items: AlignItems::End,
+            row_gap: Val::Px(4.),
+            ..default()
+        },
+        GlobalZIndex(i32::MAX - 2),
+        PickingBehavior {
+            should_block_lower: false,
+            is_hoverable: false,
+        },
+    ));
+}
+
+fn expire_toasts(time: Res<Time<Real>>, mut toasts: ResMut<Toasts>) {
+    // Ticking alone shouldn't rebuild the stack
+    for toast in toasts.bypass_change_detection().toasts.iter_mut() {
+        toast.timer.tick(time.delta());
+    }
+    if toasts.toasts.iter().any(|toast| toast.timer.finished()) {
+        toasts.toasts.retain(|toast| !toast.timer.finished());
+    }
+}
+
+fn update_toast_stack(
+    mut cmd: Commands,
+    toasts: Res<Toasts>,
+    stack: Query<(Entity, Option<&Children>), With<ToastStack>>,
+    rows: Query<&ToastRow>,
+    theme: Res<Theme>,
+) {
+    if !toasts.is_changed() {
+        return;
+    }
+    for (stack, children) in stack.iter() {
+        let existing = children
+            .into_iter()
+            .flatten()
+            .filter_map(|child| rows.get(*child).ok().map(|row| (*child, row.0)))
+            .collect::<Vec<_>>();
+        for (row, id) in existing.iter() {
+            if toasts.iter().all(|toast| toast.id != *id) {
+                cmd.entity(*row).despawn_recursive();
+            }
+        }
+        for toast in toasts.iter() {
+            if existing.iter().any(|(_, id)| *id == toast.id) {
+                continue;
+            }
+            cmd.spawn((
+                ToastRow(toast.id),
+                Node {
+                    border: UiRect::left(Val::Px(3.)),
+                    padding: UiRect::axes(Val::Px(6.), Val::Px(4.)),
+                    ..default()
+                },
+                BackgroundColor(theme.panel_background),
+                BorderColor(toast.level.color()),
+            ))
+            .with_children(|parent| {
+                parent.spawn((
+                    TextUI::new(toast.message.clone()),
+                    TextColor(toast.level.color()),
+                ));
+            })
+            .set_parent(stack)
+            .observe(dismiss_toast);
+        }
+    }
+}
+
+fn dismiss_toast(
+    trigger: Trigger<Pointer<Click>>,
+    row: Query<&ToastRow>,
+    mut toasts: ResMut<Toasts>,
+) {
+    if let Ok(row) = row.get(trigger.entity()) {
+        toasts.dismiss(row.0);
+    }
+}
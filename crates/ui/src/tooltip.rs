@@ -0,0 +1,127 @@
+use std::time::Duration;
+
+use bevy::{
+    picking::{focus::HoverMap, pointer::PointerId},
+    prelude::*,
+    window::PrimaryWindow,
+};
+
+use crate::{TextUI, Theme};
+
+pub struct TooltipPlugin;
+
+impl Plugin for TooltipPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<TooltipHover>()
+            .add_systems(Update, update_tooltip);
+    }
+}
+
+/// Text shown next to the cursor after hovering the widget for a moment.
+/// When widgets with tooltips are nested the innermost one is shown.
+#[derive(Component, Clone)]
+pub struct Tooltip(String);
+
+impl Tooltip {
+    pub fn new(text: impl Into<String>) -> Self {
+        Self(text.into())
+    }
+}
+
+#[derive(Resource, Default)]
+struct TooltipHover {
+    target: Option<Entity>,
+    elapsed: Duration,
+    popup: Option<Entity>,
+    dismissed: bool,
+}
+
+#[derive(Component)]
+struct TooltipPopup;
+
+const TOOLTIP_DELAY: Duration = Duration::from_millis(500);
+const TOOLTIP_OFFSET: Vec2 = Vec2::new(12., 16.);
+
+fn update_tooltip(
+    mut cmd: Commands,
+    time: Res<Time<Real>>,
+    hover_map: Res<HoverMap>,
+    mouse_input: Res<ButtonInput<MouseButton>>,
+    parent: Query<&Parent>,
+    tooltip: Query<&Tooltip>,
+    window: Query<&Window, With<PrimaryWindow>>,
+    mut popup: Query<&mut Node, With<TooltipPopup>>,
+    mut hover: ResMut<TooltipHover>,
+    ui_scale: Res<UiScale>,
+    theme: Res<Theme>,
+) {
+    // The hover map is unordered, pick the deepest tooltip in the hierarchy
+    // so the innermost one wins no matter which hovered entity comes first
+    let target = hover_map.get(&PointerId::Mouse).and_then(|hovered| {
+        hovered
+            .keys()
+            .filter_map(|entity| {
+                std::iter::once(*entity)
+                    .chain(parent.iter_ancestors(*entity))
+                    .find(|entity| tooltip.contains(*entity))
+            })
+            .max_by_key(|entity| (parent.iter_ancestors(*entity).count(), *entity))
+    });
+    if target != hover.target {
+        hover.target = target;
+        hover.elapsed = Duration::ZERO;
+        hover.dismissed = false;
+    } else {
+        hover.elapsed += time.delta();
+    }
+    // Clicking hides the tooltip until another widget is hovered
+    if mouse_input.get_just_pressed().next().is_some() {
+        hover.dismissed = true;
+    }
+
+    let show = target.is_some() && !hover.dismissed && hover.elapsed >= TOOLTIP_DELAY;
+    let cursor = window
+        .get_single()
+        .ok()
+        .and_then(|window| window.cursor_position());
+    match (show, hover.popup, cursor) {
+        (true, Some(entity), Some(cursor)) => {
+            if let Ok(mut node) = popup.get_mut(entity) {
+                let position = cursor / ui_scale.0 + TOOLTIP_OFFSET;
+                node.left = Val::Px(position.x);
+                node.top = Val::Px(position.y);
+            }
+        }
+        (true, None, Some(cursor)) => {
+            let Tooltip(text) = tooltip.get(target.unwrap()).unwrap();
+            let position = cursor / ui_scale.0 + TOOLTIP_OFFSET;
+            let entity = cmd
+                .spawn((
+                    TooltipPopup,
+                    Node {
+                        position_type: PositionType::Absolute,
+                        left: Val::Px(position.x),
+                        top: Val::Px(position.y),
+                        max_width: Val::Px(220.),
+                        border: UiRect::all(Val::Px(1.)),
+                        padding: UiRect::all(Val::Px(4.)),
+                        ..default()
+                    },
+                    BackgroundColor(theme.panel_background),
+                    BorderColor(theme.border),
+                    GlobalZIndex(i32::MAX),
+                    PickingBehavior::IGNORE,
+                ))
+                .with_children(|parent| {
+                    parent.spawn((TextUI::new(text.clone()), PickingBehavior::IGNORE));
+                })
+                .id();
+            hover.popup = Some(entity);
+        }
+        (false, Some(entity), _) | (true, Some(entity), None) => {
+            cmd.entity(entity).despawn_recursive();
+            hover.popup = None;
+        }
+        _ => {}
+    }
+}
@@ -0,0 +1,175 @@
+use std::time::Duration;
+
+use bevy::prelude::*;
+use bevy_simple_text_input::{TextInputInactive, TextInputValue};
+
+use crate::{InputUI, InputUISubmitEvent, no_input_focused};
+
+pub struct UndoPlugin;
+
+impl Plugin for UndoPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<UndoHistory>()
+            .add_systems(
+                Update,
+                (track_committed_value, undo_redo.run_if(no_input_focused)),
+            )
+            .add_observer(record_edit);
+    }
+}
+
+/// Excludes an input from undo, e.g. the console prompt.
+#[derive(Component)]
+pub struct NoUndo;
+
+/// Value of an input as of the last submit or outside change.
+#[derive(Component)]
+struct CommittedValue(String);
+
+struct Edit {
+    input: Entity,
+    before: String,
+    after: String,
+    time: Duration,
+}
+
+/// Submitted input edits, undone with Ctrl+Z and redone with Ctrl+Shift+Z.
+/// Undo and redo submit the input again, so whatever reacts to the submit
+/// follows along. Edits of the same input within `merge_window` are merged
+/// into one step.
+#[derive(Resource)]
+pub struct UndoHistory {
+    undo: Vec<Edit>,
+    redo: Vec<Edit>,
+    pub merge_window: Duration,
+    pub capacity: usize,
+}
+
+impl UndoHistory {
+    pub fn can_undo(&self) -> bool {
+        !self.undo.is_empty()
+    }
+
+    pub fn can_redo(&self) -> bool {
+        !self.redo.is_empty()
+    }
+
+    pub fn clear(&mut self) {
+        self.undo.clear();
+        self.redo.clear();
+    }
+
+    fn push(&mut self, input: Entity, before: String, after: String, time: Duration) {
+        self.redo.clear();
+        if let Some(last) = self.undo.last_mut() {
+            if last.input == input && time.saturating_sub(last.time) <= self.merge_window {
+                last.after = after;
+                last.time = time;
+                if last.before == last.after {
+                    self.undo.pop();
+                }
+                return;
+            }
+        }
+        if self.undo.len() >= self.capacity {
+            self.undo.remove(0);
+        }
+        self.undo.push(Edit {
+            input,
+            before,
+            after,
+            time,
+        });
+    }
+}
+
+impl Default for UndoHistory {
+    fn default() -> Self {
+        Self {
+            undo: vec![],
+            redo: vec![],
+            merge_window: Duration::from_secs(1),
+            capacity: 100,
+        }
+    }
+}
+
+fn track_committed_value(
+    mut cmd: Commands,
+    new_input: Query<
+        (Entity, &TextInputValue),
+        (With<InputUI>, Without<CommittedValue>, Without<NoUndo>),
+    >,
+    mut input: Query<
+        (&TextInputValue, &TextInputInactive, &mut CommittedValue),
+        Changed<TextInputValue>,
+    >,
+) {
+    for (entity, value) in new_input.iter() {
+        cmd.entity(entity).insert(CommittedValue(value.0.clone()));
+    }
+    // Values set by the app while the input isn't being edited aren't edits
+    for (value, inactive, mut committed) in input.iter_mut() {
+        if inactive.0 && committed.0 != value.0 {
+            committed.0 = value.0.clone();
+        }
+    }
+}
+
+fn record_edit(
+    trigger: Trigger<InputUISubmitEvent>,
+    mut input: Query<(&TextInputValue, &mut CommittedValue)>,
+    mut history: ResMut<UndoHistory>,
+    time: Res<Time<Real>>,
+) {
+    let Ok((value, mut committed)) = input.get_mut(trigger.entity()) else {
+        return;
+    };
+    if committed.0 == value.0 {
+        return;
+    }
+    let before = std::mem::replace(&mut committed.0, value.0.clone());
+    history.push(trigger.entity(), before, value.0.clone(), time.elapsed());
+}
+
+fn undo_redo(
+    mut cmd: Commands,
+    key_input: Res<ButtonInput<KeyCode>>,
+    mut history: ResMut<UndoHistory>,
+    mut input: Query<(&mut TextInputValue, &mut CommittedValue)>,
+) {
+    if !key_input.any_pressed([KeyCode::ControlLeft, KeyCode::ControlRight]) {
+        return;
+    }
+    let shift = key_input.any_pressed([KeyCode::ShiftLeft, KeyCode::ShiftRight]);
+    let undo = !shift && key_input.just_pressed(KeyCode::KeyZ);
+    let redo = shift && key_input.just_pressed(KeyCode::KeyZ);
+    if !undo && !redo {
+        return;
+    }
+    loop {
+        let edit = if undo {
+            history.undo.pop()
+        } else {
+            history.redo.pop()
+        };
+        let Some(edit) = edit else {
+            break;
+        };
+        // Inputs despawned since the edit can't be restored
+        let Ok((mut value, mut committed)) = input.get_mut(edit.input) else {
+            continue;
+        };
+        let text = if undo { &edit.before } else { &edit.after };
+        // Committed first so the submit below isn't recorded as a new edit
+        value.0 = text.clone();
+        committed.0 = text.clone();
+        cmd.trigger_targets(InputUISubmitEvent, edit.input);
+        if undo {
+            history.redo.push(edit);
+        } else {
+            history.undo.push(edit);
+        }
+        break;
+    }
+}
@@ -0,0 +1,263 @@
+use std::marker::PhantomData;
+
+use bevy::prelude::*;
+use bevy_simple_text_input::{TextInputInactive, TextInputValue};
+
+use crate::{
+    InputField, InputFieldFilter, InputUI, InputUISubmitEvent, InputUInitialValue, Level, Toasts,
+};
+
+/// Adds [`ValueField`]s of type `T`, the types implemented by this crate are
+/// added by [`PlaygroundUIPlugin`](crate::PlaygroundUIPlugin).
+pub struct ValueFieldPlugin<T>(PhantomData<T>);
+
+impl<T> Default for ValueFieldPlugin<T> {
+    fn default() -> Self {
+        Self(PhantomData)
+    }
+}
+
+impl<T: FieldValue> Plugin for ValueFieldPlugin<T> {
+    fn build(&self, app: &mut App) {
+        app.add_systems(Update, sync_value_field::<T>)
+            .add_observer(create_value_field::<T>)
+            .add_observer(submit_value_field::<T>);
+    }
+}
+
+/// Value that can be edited through a [`ValueField`].
+pub trait FieldValue: Clone + Send + Sync + 'static {
+    fn parse(text: &str) -> Option<Self>;
+
+    fn format(&self) -> String;
+
+    /// Whether the text is allowed while typing, it doesn't have to parse yet,
+    /// e.g. a lone "-".
+    fn accepts(_text: &str) -> bool {
+        true
+    }
+}
+
+macro_rules! impl_field_value_int {
+    ($($ty:ty: $signed:expr),*) => {$(
+        impl FieldValue for $ty {
+            fn parse(text: &str) -> Option<Self> {
+                text.parse().ok()
+            }
+
+            fn format(&self) -> String {
+                self.to_string()
+            }
+
+            fn accepts(text: &str) -> bool {
+                accepts_number(text, $signed, |text| text.parse::<$ty>().is_ok())
+            }
+        }
+    )*};
+}
+
+macro_rules! impl_field_value_float {
+    ($($ty:ty),*) => {$(
+        impl FieldValue for $ty {
+            fn parse(text: &str) -> Option<Self> {
+                text.parse().ok().filter(|value: &$ty| value.is_finite())
+            }
+
+            fn format(&self) -> String {
+                self.to_string()
+            }
+
+            fn accepts(text: &str) -> bool {
+                matches!(text, "." | "-.")
+                    || accepts_number(text, true, |text| text.parse::<$ty>().is_ok())
+            }
+        }
+    )*};
+}
+
+fn accepts_number(text: &str, signed: bool, parses: fn(&str) -> bool) -> bool {
+    text.is_empty() || (signed && text == "-") || parses(text)
+}
+
+impl_field_value_int!(u32: false, i32: true, i64: true);
+impl_field_value_float!(f32, f64);
+
+impl FieldValue for bool {
+    fn parse(text: &str) -> Option<Self> {
+        text.parse().ok()
+    }
+
+    fn format(&self) -> String {
+        self.to_string()
+    }
+
+    fn accepts(text: &str) -> bool {
+        "true".starts_with(text) || "false".starts_with(text)
+    }
+}
+
+impl FieldValue for String {
+    fn parse(text: &str) -> Option<Self> {
+        Some(text.to_string())
+    }
+
+    fn format(&self) -> String {
+        self.clone()
+    }
+}
+
+type Validator<T> = Box<dyn Fn(&T) -> Result<(), String> + Send + Sync>;
+
+/// [`InputField`] bound to a value of type `T`. Submitting parses and
+/// validates the text, then triggers [`ValueSubmitted`] on this entity.
+/// Invalid text is reverted and reported as a toast. Needs an
+/// [`InputFieldLabel`](crate::InputFieldLabel) and a `Name` like any other
+/// input field.
+///
+/// ```ignore
+/// cmd.spawn((
+///     ValueField::new(config.size).with_range(1, 50),
+///     InputFieldLabel::new("size"),
+///     Name::new("particle:size"),
+/// ))
+/// .observe(|trigger: Trigger<ValueSubmitted<u32>>, mut config: ResMut<Config>| {
+///     config.size = trigger.value;
+/// });
+/// ```
+#[derive(Component)]
+#[require(Node)]
+pub struct ValueField<T: FieldValue> {
+    value: T,
+    validators: Vec<Validator<T>>,
+}
+
+impl<T: FieldValue> ValueField<T> {
+    pub fn new(value: T) -> Self {
+        Self {
+            value,
+            validators: vec![],
+        }
+    }
+
+    /// Rejects values the function returns an error for.
+    pub fn with_validator(
+        mut self,
+        validator: impl Fn(&T) -> Result<(), String> + Send + Sync + 'static,
+    ) -> Self {
+        self.validators.push(Box::new(validator));
+        self
+    }
+
+    pub fn value(&self) -> &T {
+        &self.value
+    }
+
+    /// Sets the value without triggering [`ValueSubmitted`], the input is
+    /// updated unless it's being edited.
+    pub fn set(&mut self, value: T) {
+        self.value = value;
+    }
+
+    pub fn validate(&self, value: &T) -> Result<(), String> {
+        self.validators
+            .iter()
+            .try_for_each(|validator| validator(value))
+    }
+}
+
+impl<T: FieldValue + PartialOrd> ValueField<T> {
+    pub fn with_min(self, min: T) -> Self {
+        self.with_validator(move |value| {
+            if *value < min {
+                return Err(format!("must be at least {}", min.format()));
+            }
+            Ok(())
+        })
+    }
+
+    pub fn with_max(self, max: T) -> Self {
+        self.with_validator(move |value| {
+            if *value > max {
+                return Err(format!("must be at most {}", max.format()));
+            }
+            Ok(())
+        })
+    }
+
+    pub fn with_range(self, min: T, max: T) -> Self {
+        self.with_min(min).with_max(max)
+    }
+}
+
+/// Triggered on the [`ValueField`] entity when a valid value is submitted.
+#[derive(Event)]
+pub struct ValueSubmitted<T: FieldValue> {
+    pub value: T,
+    pub previous: T,
+}
+
+fn create_value_field<T: FieldValue>(
+    trigger: Trigger<OnAdd, ValueField<T>>,
+    mut cmd: Commands,
+    field: Query<&ValueField<T>>,
+) {
+    let field = field.get(trigger.entity()).unwrap();
+    cmd.entity(trigger.entity()).insert((
+        InputField,
+        InputUInitialValue(field.value.format()),
+        InputFieldFilter(T::accepts),
+    ));
+}
+
+fn submit_value_field<T: FieldValue>(
+    trigger: Trigger<InputUISubmitEvent>,
+    mut cmd: Commands,
+    mut input: Query<(&mut TextInputValue, &Parent), With<InputUI>>,
+    mut field: Query<(&mut ValueField<T>, Option<&Name>)>,
+    toasts: Option<ResMut<Toasts>>,
+) {
+    let Ok((mut text, parent)) = input.get_mut(trigger.entity()) else {
+        return;
+    };
+    let Ok((mut field, name)) = field.get_mut(parent.get()) else {
+        return;
+    };
+    let value = T::parse(text.0.trim())
+        .ok_or_else(|| format!("'{}' isn't a valid value", text.0.trim()))
+        .and_then(|value| field.validate(&value).map(|_| value));
+    match value {
+        Ok(value) => {
+            let previous = std::mem::replace(&mut field.value, value.clone());
+            cmd.trigger_targets(ValueSubmitted { value, previous }, parent.get());
+        }
+        Err(err) => {
+            text.0 = field.value.format();
+            let message = match name {
+                Some(name) => format!("{}: {}", name, err),
+                None => err,
+            };
+            match toasts {
+                Some(mut toasts) => {
+                    toasts.push(Level::Warn, message);
+                }
+                None => warn!("{}", message),
+            }
+        }
+    }
+}
+
+fn sync_value_field<T: FieldValue>(
+    field: Query<(&ValueField<T>, &Children), Changed<ValueField<T>>>,
+    mut input: Query<(&mut TextInputValue, &TextInputInactive), With<InputUI>>,
+) {
+    for (field, children) in field.iter() {
+        let text = field.value.format();
+        let mut inputs = input.iter_many_mut(children);
+        while let Some((mut value, inactive)) = inputs.fetch_next() {
+            // Don't pull the text from under the cursor
+            if inactive.0 && value.0 != text {
+                value.0 = text.clone();
+            }
+        }
+    }
+}
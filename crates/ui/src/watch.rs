@@ -0,0 +1,370 @@
+use std::collections::VecDeque;
+
+use bevy::{
+    asset::RenderAssetUsages,
+    prelude::*,
+    render::render_resource::{Extent3d, TextureDimension, TextureFormat},
+};
+
+use crate::TextUI;
+
+pub struct WatchPlugin;
+
+impl Plugin for WatchPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            Update,
+            (update_watch_view, update_plot).run_if(resource_exists::<DebugWatch>),
+        )
+        .add_observer(create_watch_view)
+        .add_observer(create_plot);
+    }
+}
+
+/// Values that can be shown by [`DebugWatch`], numbers also get plotted.
+pub trait WatchValue {
+    fn watch_text(&self) -> String;
+
+    fn plot_value(&self) -> Option<f32> {
+        None
+    }
+}
+
+macro_rules! impl_watch_value_number {
+    ($($ty:ty),*) => {
+        $(
+            impl WatchValue for $ty {
+                fn watch_text(&self) -> String {
+                    self.to_string()
+                }
+
+                fn plot_value(&self) -> Option<f32> {
+                    Some(*self as f32)
+                }
+            }
+        )*
+    };
+}
+
+impl_watch_value_number!(f32, f64, i32, i64, u32, u64, usize);
+
+impl WatchValue for bool {
+    fn watch_text(&self) -> String {
+        self.to_string()
+    }
+}
+
+impl WatchValue for &str {
+    fn watch_text(&self) -> String {
+        self.to_string()
+    }
+}
+
+impl WatchValue for String {
+    fn watch_text(&self) -> String {
+        self.clone()
+    }
+}
+
+impl WatchValue for Vec2 {
+    fn watch_text(&self) -> String {
+        format!("{:.1}, {:.1}", self.x, self.y)
+    }
+}
+
+pub struct Watch {
+    pub text: String,
+    pub history: VecDeque<f32>,
+}
+
+impl Watch {
+    /// Min, max and average of the newest `last` samples.
+    pub fn stats(&self, last: usize) -> Option<(f32, f32, f32)> {
+        let count = self.history.len().min(last);
+        if count == 0 {
+            return None;
+        }
+        let (min, max, sum) = self
+            .history
+            .iter()
+            .rev()
+            .take(count)
+            .fold((f32::MAX, f32::MIN, 0.), |(min, max, sum), value| {
+                (min.min(*value), max.max(*value), sum + value)
+            });
+        Some((min, max, sum / count as f32))
+    }
+}
+
+/// Labelled values that are updated in place, e.g. `watch("particles", len)`.
+/// Keys are listed in the order they were first watched.
+#[derive(Resource)]
+pub struct DebugWatch {
+    watches: Vec<(String, Watch)>,
+    history_len: usize,
+}
+
+impl DebugWatch {
+    pub fn new() -> Self {
+        Self::with_history_len(240)
+    }
+
+    pub fn with_history_len(history_len: usize) -> Self {
+        Self {
+            watches: vec![],
+            history_len: history_len.max(2),
+        }
+    }
+
+    pub fn watch(&mut self, key: impl Into<String>, value: impl WatchValue) {
+        let key = key.into();
+        let index = match self.watches.iter().position(|(k, _)| *k == key) {
+            Some(index) => index,
+            None => {
+                self.watches.push((
+                    key,
+                    Watch {
+                        text: String::new(),
+                        history: VecDeque::with_capacity(self.history_len),
+                    },
+                ));
+                self.watches.len() - 1
+            }
+        };
+        let watch = &mut self.watches[index].1;
+        watch.text = value.watch_text();
+        if let Some(value) = value.plot_value() {
+            if watch.history.len() >= self.history_len {
+                watch.history.pop_front();
+            }
+            watch.history.push_back(value);
+        }
+    }
+
+    pub fn get(&self, key: &str) -> Option<&Watch> {
+        self.watches
+            .iter()
+            .find_map(|(k, watch)| (k == key).then_some(watch))
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &Watch)> {
+        self.watches.iter().map(|(k, watch)| (k.as_str(), watch))
+    }
+
+    pub fn remove(&mut self, key: &str) {
+        self.watches.retain(|(k, _)| k != key);
+    }
+}
+
+impl Default for DebugWatch {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Lists every [`DebugWatch`] value as a `key: value` row.
+#[derive(Component)]
+#[require(Node)]
+pub struct DebugWatchView;
+
+#[derive(Component)]
+struct WatchRow;
+
+fn create_watch_view(trigger: Trigger<OnAdd, DebugWatchView>, mut cmd: Commands) {
+    cmd.entity(trigger.entity()).insert(Node {
+        flex_direction: FlexDirection::Column,
+        ..default()
+    });
+}
+
+fn update_watch_view(
+    mut cmd: Commands,
+    debug_watch: Res<DebugWatch>,
+    view: Query<(Entity, Option<&Children>), With<DebugWatchView>>,
+    mut rows: Query<&mut Text, With<WatchRow>>,
+) {
+    if !debug_watch.is_changed() {
+        return;
+    }
+    for (view, children) in view.iter() {
+        let mut existing = children.into_iter().flatten().copied();
+        for (key, watch) in debug_watch.iter() {
+            let line = format!("{}: {}", key, watch.text);
+            match existing.next() {
+                Some(row) => {
+                    if let Ok(mut text) = rows.get_mut(row) {
+                        if text.0 != line {
+                            text.0 = line;
+                        }
+                    }
+                }
+                None => {
+                    cmd.spawn((WatchRow, TextUI::new(line))).set_parent(view);
+                }
+            }
+        }
+        for row in existing {
+            cmd.entity(row).despawn_recursive();
+        }
+    }
+}
+
+/// Rolling line graph of a [`DebugWatch`] key history with min, max and
+/// average of the visible samples.
+#[derive(Component)]
+#[require(Node)]
+pub struct Plot {
+    pub key: String,
+    pub size: UVec2,
+    pub color: Color,
+}
+
+impl Plot {
+    pub fn new(key: impl Into<String>) -> Self {
+        Self {
+            key: key.into(),
+            size: UVec2::new(160, 40),
+            color: Color::WHITE,
+        }
+    }
+
+    pub fn with_size(mut self, width: u32, height: u32) -> Self {
+        self.size = UVec2::new(width.max(2), height.max(2));
+        self
+    }
+
+    pub fn with_color(mut self, color: Color) -> Self {
+        self.color = color;
+        self
+    }
+}
+
+#[derive(Component)]
+struct PlotParts {
+    image: Handle<Image>,
+    stats: Entity,
+}
+
+fn create_plot(
+    trigger: Trigger<OnAdd, Plot>,
+    mut cmd: Commands,
+    plot: Query<&Plot>,
+    mut images: ResMut<Assets<Image>>,
+) {
+    let plot = plot.get(trigger.entity()).unwrap();
+    let image = images.add(Image::new_fill(
+        Extent3d {
+            width: plot.size.x,
+            height: plot.size.y,
+            depth_or_array_layers: 1,
+        },
+        TextureDimension::D2,
+        &[0, 0, 0, 0],
+        TextureFormat::Rgba8UnormSrgb,
+        RenderAssetUsages::default(),
+    ));
+    let mut stats = Entity::PLACEHOLDER;
+    cmd.entity(trigger.entity())
+        .insert((
+            Node {
+                flex_direction: FlexDirection::Column,
+                ..default()
+            },
+            BorderColor(Color::WHITE),
+        ))
+        .with_children(|parent| {
+            parent.spawn(TextUI::new(plot.key.clone()));
+            parent.spawn((
+                ImageNode::new(image.clone()),
+                Node {
+                    width: Val::Px(plot.size.x as f32),
+                    height: Val::Px(plot.size.y as f32),
+                    border: UiRect::all(Val::Px(1.)),
+                    ..default()
+                },
+                BorderColor(Color::srgb(0.5, 0.5, 0.5)),
+            ));
+            stats = parent.spawn(TextUI::new("")).id();
+        });
+    cmd.entity(trigger.entity())
+        .insert(PlotParts { image, stats });
+}
+
+fn update_plot(
+    debug_watch: Res<DebugWatch>,
+    plot: Query<(&Plot, &PlotParts)>,
+    mut images: ResMut<Assets<Image>>,
+    mut text: Query<&mut Text>,
+) {
+    if !debug_watch.is_changed() {
+        return;
+    }
+    for (plot, parts) in plot.iter() {
+        let Some(watch) = debug_watch.get(&plot.key) else {
+            continue;
+        };
+        // Only as many samples as there are pixel columns fit
+        let visible = watch.history.len().min(plot.size.x as usize);
+        let Some((min, max, avg)) = watch.stats(visible) else {
+            continue;
+        };
+        if let Ok(mut text) = text.get_mut(parts.stats) {
+            text.0 = format!("min {:.2}  max {:.2}  avg {:.2}", min, max, avg);
+        }
+        let Some(image) = images.get_mut(&parts.image) else {
+            continue;
+        };
+        let (width, height) = (plot.size.x as i32, plot.size.y as i32);
+        let range = if max - min > f32::EPSILON {
+            max - min
+        } else {
+            1.
+        };
+        let to_pixel = |i: usize, value: f32| {
+            // Newest sample on the right edge
+            let x = width - visible as i32 + i as i32;
+            let y = ((1. - (value - min) / range) * (height - 1) as f32).round() as i32;
+            IVec2::new(x, y)
+        };
+        let color = plot.color.to_srgba().to_u8_array();
+        image.data.fill(0);
+        let mut previous = None;
+        let skipped = watch.history.len() - visible;
+        for (i, value) in watch.history.iter().skip(skipped).enumerate() {
+            let point = to_pixel(i, *value);
+            draw_line(
+                &mut image.data,
+                plot.size,
+                previous.unwrap_or(point),
+                point,
+                color,
+            );
+            previous = Some(point);
+        }
+    }
+}
+
+fn draw_line(data: &mut [u8], size: UVec2, from: IVec2, to: IVec2, color: [u8; 4]) {
+    let delta = (to - from).abs();
+    let step = IVec2::new((to.x - from.x).signum(), (to.y - from.y).signum());
+    let mut error = delta.x - delta.y;
+    let mut point = from;
+    loop {
+        if point.x >= 0 && point.y >= 0 && point.x < size.x as i32 && point.y < size.y as i32 {
+            let index = (point.y as usize * size.x as usize + point.x as usize) * 4;
+            data[index..index + 4].copy_from_slice(&color);
+        }
+        if point == to {
+            break;
+        }
+        let error2 = error * 2;
+        if error2 > -delta.y {
+            error -= delta.y;
+            point.x += step.x;
+        }
+        if error2 < delta.x {
+            error += delta.x;
+            point.y += step.y;
+        }
+    }
+}
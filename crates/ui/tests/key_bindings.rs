@@ -0,0 +1,97 @@
+use bevy::{ecs::system::RunSystemOnce, prelude::*};
+use playground_ui::{
+    ActionInput, Binding, KeyBindings, KeyBindingsAppExt, KeyBindingsView, testing::UiTestApp,
+};
+
+fn setup() -> UiTestApp {
+    let mut app = UiTestApp::new();
+    app.app
+        .register_action("fire", [KeyCode::KeyJ])
+        .register_action("jump", [KeyCode::Space]);
+    app.spawn(KeyBindingsView);
+    app
+}
+
+/// Button of the binding slot labeled `label` in the row of `action`.
+fn slot(app: &mut UiTestApp, action: &str, label: &str) -> Entity {
+    let mut text = app.world_mut().query::<(Entity, &Text, &Parent)>();
+    let texts = text
+        .iter(app.world())
+        .map(|(entity, text, parent)| (entity, text.0.clone(), parent.get()))
+        .collect::<Vec<_>>();
+    let row = texts
+        .iter()
+        .find(|(_, text, _)| text == action)
+        .map(|(entity, ..)| app.world().get::<Parent>(*entity).unwrap().get())
+        .unwrap();
+    texts
+        .iter()
+        .map(|(_, text, button)| (text, *button))
+        .find(|(text, button)| {
+            *text == label && app.world().get::<Parent>(*button).map(Parent::get) == Some(row)
+        })
+        .map(|(_, button)| button)
+        .unwrap()
+}
+
+fn pressed(app: &mut UiTestApp, action: &'static str) -> bool {
+    app.world_mut()
+        .run_system_once(move |input: ActionInput| input.pressed(action))
+        .unwrap()
+}
+
+#[test]
+fn action_follows_binding() {
+    let mut app = setup();
+
+    app.hold_key(KeyCode::KeyJ);
+    assert!(pressed(&mut app, "fire"));
+    assert!(!pressed(&mut app, "jump"));
+}
+
+#[test]
+fn clicking_slot_captures_next_key() {
+    let mut app = setup();
+
+    let add = slot(&mut app, "fire", "+");
+    app.click(add);
+    app.press_key(KeyCode::KeyK);
+    let bindings = app.world().resource::<KeyBindings>();
+    assert_eq!(bindings.bindings("fire"), &[
+        Binding::Key(KeyCode::KeyJ),
+        Binding::Key(KeyCode::KeyK)
+    ]);
+
+    let j = slot(&mut app, "fire", "J");
+    app.click(j);
+    app.press_key(KeyCode::Backspace);
+    let bindings = app.world().resource::<KeyBindings>();
+    assert_eq!(bindings.bindings("fire"), &[Binding::Key(KeyCode::KeyK)]);
+}
+
+#[test]
+fn escape_cancels_capture() {
+    let mut app = setup();
+
+    let j = slot(&mut app, "fire", "J");
+    app.click(j);
+    app.press_key(KeyCode::Escape);
+    let bindings = app.world().resource::<KeyBindings>();
+    assert_eq!(bindings.bindings("fire"), &[Binding::Key(KeyCode::KeyJ)]);
+}
+
+#[test]
+fn shared_binding_is_a_conflict() {
+    let mut app = setup();
+
+    let space = slot(&mut app, "jump", "Space");
+    app.click(space);
+    app.press_key(KeyCode::KeyJ);
+    let bindings = app.world().resource::<KeyBindings>();
+    assert_eq!(bindings.conflicts("fire"), [(
+        Binding::Key(KeyCode::KeyJ),
+        "jump"
+    )]);
+    // Panics if the binding isn't marked
+    slot(&mut app, "fire", "! J");
+}
@@ -0,0 +1,81 @@
+use bevy::prelude::*;
+use playground_ui::{InputFieldLabel, Toasts, ValueField, ValueSubmitted, testing::UiTestApp};
+
+#[derive(Resource, Default)]
+struct Submitted(Vec<(u32, u32)>);
+
+fn spawn_field(app: &mut UiTestApp, field: ValueField<u32>) -> Entity {
+    app.world_mut().init_resource::<Submitted>();
+    let entity = app
+        .world_mut()
+        .spawn((field, InputFieldLabel::new("count"), Name::new("count")))
+        .observe(
+            |trigger: Trigger<ValueSubmitted<u32>>, mut submitted: ResMut<Submitted>| {
+                submitted.0.push((trigger.value, trigger.previous));
+            },
+        )
+        .id();
+    app.update();
+    entity
+}
+
+fn submit(app: &mut UiTestApp, text: &str) {
+    let input = app.input("count");
+    app.click(input);
+    app.set_value(input, "");
+    app.type_text(text);
+    app.press_key(KeyCode::Enter);
+}
+
+#[test]
+fn submit_triggers_parsed_value_and_previous() {
+    let mut app = UiTestApp::new();
+    let field = spawn_field(&mut app, ValueField::new(3));
+
+    let input = app.input("count");
+    assert_eq!(app.value(input), "3");
+    submit(&mut app, "12");
+    assert_eq!(app.world().resource::<Submitted>().0, [(12, 3)]);
+    assert_eq!(
+        *app.world().get::<ValueField<u32>>(field).unwrap().value(),
+        12
+    );
+}
+
+#[test]
+fn out_of_range_value_is_reverted() {
+    let mut app = UiTestApp::new();
+    spawn_field(&mut app, ValueField::new(3).with_range(1, 10));
+
+    submit(&mut app, "42");
+    let input = app.input("count");
+    assert_eq!(app.value(input), "3");
+    assert!(app.world().resource::<Submitted>().0.is_empty());
+    assert_eq!(app.world().resource::<Toasts>().iter().count(), 1);
+}
+
+#[test]
+fn unsigned_field_rejects_minus() {
+    let mut app = UiTestApp::new();
+    spawn_field(&mut app, ValueField::new(0));
+
+    let input = app.input("count");
+    app.click(input);
+    app.set_value(input, "");
+    app.type_text("-5");
+    assert_eq!(app.value(input), "5");
+}
+
+#[test]
+fn set_updates_input() {
+    let mut app = UiTestApp::new();
+    let field = spawn_field(&mut app, ValueField::new(3));
+
+    app.world_mut()
+        .get_mut::<ValueField<u32>>(field)
+        .unwrap()
+        .set(7);
+    app.update();
+    let input = app.input("count");
+    assert_eq!(app.value(input), "7");
+}
@@ -0,0 +1,247 @@
+use bevy::prelude::*;
+use playground_ui::{
+    ButtonPressed, ButtonShortcut, Disabled, InputField, InputFieldLabel, InputFieldType,
+    InputFieldUnit, InputUISubmitEvent, InputUInitialValue, KeepFocusOnSubmit, Panel, PanelChrome,
+    PanelLayout, PanelTitle, TextButton, TextUI, testing::UiTestApp,
+};
+
+#[test]
+fn input_field_builds_label_and_input() {
+    let mut app = UiTestApp::new();
+    let input = app.spawn_input_field("speed", "1.5");
+    let field = app.world().get::<Parent>(input).unwrap().get();
+
+    assert_eq!(app.value(input), "1.5");
+    assert!(!app.is_active(input));
+    assert!(app.world().get::<InputUInitialValue>(field).is_none());
+    let children = app.world().get::<Children>(field).unwrap();
+    assert_eq!(children.len(), 2);
+    assert_eq!(app.text(children[0]), "speed");
+    assert_eq!(children[1], input);
+}
+
+#[test]
+fn input_field_unit_is_shown_after_input() {
+    let mut app = UiTestApp::new();
+    let field = app.spawn((
+        InputField,
+        InputFieldLabel::new("size"),
+        InputFieldType::F32,
+        InputFieldUnit("px".into()),
+        Name::new("size"),
+    ));
+    let input = app.input("size");
+
+    let children = app.world().get::<Children>(field).unwrap();
+    assert_eq!(children.len(), 3);
+    assert_eq!(children[1], input);
+    assert_eq!(app.text(children[2]), "px");
+}
+
+#[test]
+fn click_focuses_input() {
+    let mut app = UiTestApp::new();
+    let input = app.spawn_input_field("a", "");
+    assert_eq!(app.focused(), None);
+
+    app.click(input);
+    assert_eq!(app.focused(), Some(input));
+    assert!(app.is_active(input));
+}
+
+#[test]
+fn click_moves_focus_between_inputs() {
+    let mut app = UiTestApp::new();
+    let a = app.spawn_input_field("a", "");
+    let b = app.spawn_input_field("b", "");
+
+    app.click(a);
+    app.click(b);
+    assert_eq!(app.focused(), Some(b));
+    assert!(!app.is_active(a));
+    assert!(app.is_active(b));
+}
+
+#[test]
+fn click_outside_drops_focus() {
+    let mut app = UiTestApp::new();
+    let input = app.spawn_input_field("a", "");
+    let other = app.spawn((Node::default(), Interaction::None));
+
+    app.click(input);
+    app.click(other);
+    assert_eq!(app.focused(), None);
+    assert!(!app.is_active(input));
+}
+
+#[test]
+fn typing_edits_focused_input_only() {
+    let mut app = UiTestApp::new();
+    let a = app.spawn_input_field("a", "");
+    let b = app.spawn_input_field("b", "");
+
+    app.type_text("ignored");
+    assert_eq!(app.value(a), "");
+
+    app.click(a);
+    app.type_text("hello");
+    assert_eq!(app.value(a), "hello");
+    assert_eq!(app.value(b), "");
+
+    app.press_key(KeyCode::Backspace);
+    assert_eq!(app.value(a), "hell");
+}
+
+#[test]
+fn number_input_rejects_other_characters() {
+    let mut app = UiTestApp::new();
+    app.spawn((
+        InputField,
+        InputFieldLabel::new("count"),
+        InputFieldType::I32,
+        Name::new("count"),
+    ));
+    let input = app.input("count");
+
+    app.click(input);
+    app.type_text("1a2.");
+    assert_eq!(app.value(input), "12");
+}
+
+#[test]
+fn enter_submits_and_unfocuses() {
+    let mut app = UiTestApp::new();
+    app.record::<InputUISubmitEvent>();
+    let input = app.spawn_input_field("a", "");
+
+    app.click(input);
+    app.type_text("42");
+    app.press_key(KeyCode::Enter);
+    assert_eq!(app.triggered::<InputUISubmitEvent>(), &[input]);
+    assert_eq!(app.value(input), "42");
+    assert_eq!(app.focused(), None);
+    assert!(!app.is_active(input));
+}
+
+#[test]
+fn enter_without_focus_submits_nothing() {
+    let mut app = UiTestApp::new();
+    app.record::<InputUISubmitEvent>();
+    app.spawn_input_field("a", "");
+
+    app.press_key(KeyCode::Enter);
+    assert!(app.triggered::<InputUISubmitEvent>().is_empty());
+}
+
+#[test]
+fn keep_focus_on_submit() {
+    let mut app = UiTestApp::new();
+    let input = app.spawn_input_field("a", "");
+    app.world_mut().entity_mut(input).insert(KeepFocusOnSubmit);
+
+    app.click(input);
+    app.press_key(KeyCode::Enter);
+    assert_eq!(app.focused(), Some(input));
+    assert!(app.is_active(input));
+}
+
+#[test]
+fn undo_restores_submitted_value() {
+    let mut app = UiTestApp::new();
+    app.record::<InputUISubmitEvent>();
+    let input = app.spawn_input_field("a", "");
+
+    app.click(input);
+    app.type_text("1");
+    app.press_key(KeyCode::Enter);
+
+    app.hold_key(KeyCode::ControlLeft);
+    app.press_key(KeyCode::KeyZ);
+    assert_eq!(app.value(input), "");
+    app.hold_key(KeyCode::ShiftLeft);
+    app.press_key(KeyCode::KeyZ);
+    assert_eq!(app.value(input), "1");
+    // Undo and redo submit again so apps pick up the value
+    assert_eq!(app.triggered::<InputUISubmitEvent>(), &[
+        input, input, input
+    ]);
+}
+
+#[test]
+fn panel_builds_title_and_separator() {
+    let mut app = UiTestApp::new();
+    let panel = app.spawn((Panel, PanelTitle::new("Stats")));
+    let content = app.world_mut().spawn(TextUI::new("content")).id();
+    app.world_mut().entity_mut(panel).add_child(content);
+    app.update();
+
+    let children = app.world().get::<Children>(panel).unwrap().to_vec();
+    assert_eq!(children.len(), 3);
+    assert!(app.world().get::<PanelChrome>(children[0]).is_some());
+    assert!(app.world().get::<PanelChrome>(children[1]).is_some());
+    assert_eq!(app.text(children[0]), "[-] Stats");
+    assert_eq!(children[2], content);
+}
+
+#[test]
+fn clicking_panel_title_collapses_it() {
+    let mut app = UiTestApp::new();
+    let panel = app.spawn((Panel, PanelTitle::new("Stats")));
+    let content = app.world_mut().spawn(TextUI::new("content")).id();
+    app.world_mut().entity_mut(panel).add_child(content);
+    app.update();
+    let title = app.world().get::<Children>(panel).unwrap()[0];
+
+    app.click(title);
+    assert!(app.world().get::<PanelLayout>(panel).unwrap().collapsed);
+    assert_eq!(app.text(title), "[+] Stats");
+    assert_eq!(
+        app.world().get::<Node>(content).unwrap().display,
+        Display::None
+    );
+
+    app.click(title);
+    assert!(!app.world().get::<PanelLayout>(panel).unwrap().collapsed);
+    assert_eq!(
+        app.world().get::<Node>(content).unwrap().display,
+        Display::Flex
+    );
+}
+
+#[test]
+fn button_click_and_shortcut_trigger_pressed() {
+    let mut app = UiTestApp::new();
+    app.record::<ButtonPressed>();
+    let button = app.spawn((TextButton::new("Spawn"), ButtonShortcut(KeyCode::KeyE)));
+
+    app.click(button);
+    app.press_key(KeyCode::KeyE);
+    assert_eq!(app.triggered::<ButtonPressed>(), &[button, button]);
+}
+
+#[test]
+fn button_shortcut_is_ignored_while_typing() {
+    let mut app = UiTestApp::new();
+    app.record::<ButtonPressed>();
+    app.spawn((TextButton::new("Spawn"), ButtonShortcut(KeyCode::KeyE)));
+    let input = app.spawn_input_field("a", "");
+
+    app.click(input);
+    app.press_key(KeyCode::KeyE);
+    assert!(app.triggered::<ButtonPressed>().is_empty());
+}
+
+#[test]
+fn disabled_button_ignores_presses() {
+    let mut app = UiTestApp::new();
+    app.record::<ButtonPressed>();
+    let button = app.spawn((
+        TextButton::new("Spawn"),
+        ButtonShortcut(KeyCode::KeyE),
+        Disabled,
+    ));
+
+    app.click(button);
+    app.press_key(KeyCode::KeyE);
+    assert!(app.triggered::<ButtonPressed>().is_empty());
+}
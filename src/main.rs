@@ -1,3 +1,134 @@
-fn main() {
-    println!("Hello, world!");
+use std::{
+    env,
+    io::{self, BufRead, Write},
+};
+
+use bevy::prelude::*;
+use playground_core::PlaygroundAppPlugin;
+
+struct Experiment {
+    name: &'static str,
+    title: &'static str,
+    description: &'static str,
+    add: fn(&mut App),
+}
+
+/// New experiment crates expose a plugin and get an entry here.
+const EXPERIMENTS: &[Experiment] = &[
+    Experiment {
+        name: "portal",
+        title: "Portal",
+        description: "Particles spiraling into a portal",
+        add: |app| {
+            app.add_plugins(portal::PortalPlugin);
+        },
+    },
+    Experiment {
+        name: "simple-space",
+        title: "Simple Space",
+        description: "Top down space shooter",
+        add: |app| {
+            app.add_plugins(simple_space::SimpleSpacePlugin);
+        },
+    },
+];
+
+const USAGE: &str = "\
+Usage: playground [run <experiment>] [--width <px>] [--height <px>] [--title <title>] [--headless] [--no-fps]
+       playground list
+
+Without a command a menu of the experiments is shown.";
+
+fn main() -> AppExit {
+    let args = env::args().skip(1).collect::<Vec<_>>();
+    let experiment = match args.first().map(String::as_str) {
+        Some("run") => match args.get(1) {
+            Some(name) => find(name),
+            None => Err("Missing experiment name".to_string()),
+        },
+        Some("list") => {
+            list();
+            return AppExit::Success;
+        }
+        Some("help" | "-h" | "--help") => {
+            println!("{}", USAGE);
+            return AppExit::Success;
+        }
+        Some(arg) if !arg.starts_with("--") => Err(format!("Unknown command '{}'", arg)),
+        _ => menu(),
+    };
+    match experiment {
+        Ok(experiment) => run(experiment),
+        Err(err) => {
+            eprintln!("{}\n\n{}", err, USAGE);
+            AppExit::error()
+        }
+    }
+}
+
+fn run(experiment: &Experiment) -> AppExit {
+    let mut app = App::new();
+    app.add_plugins(PlaygroundAppPlugin::new(experiment.title));
+    (experiment.add)(&mut app);
+    app.run()
+}
+
+fn find(name: &str) -> Result<&'static Experiment, String> {
+    EXPERIMENTS
+        .iter()
+        .find(|experiment| experiment.name == name)
+        .ok_or_else(|| {
+            let names = EXPERIMENTS
+                .iter()
+                .map(|experiment| experiment.name)
+                .collect::<Vec<_>>();
+            format!(
+                "Unknown experiment '{}', available: {}",
+                name,
+                names.join(", ")
+            )
+        })
+}
+
+fn list() {
+    for (i, experiment) in EXPERIMENTS.iter().enumerate() {
+        println!(
+            "{:>2}. {:<14} {}",
+            i + 1,
+            experiment.name,
+            experiment.description
+        );
+    }
+}
+
+/// Asks on the terminal until a valid experiment is picked, by number or
+/// name.
+fn menu() -> Result<&'static Experiment, String> {
+    println!("Playground experiments:");
+    list();
+    let stdin = io::stdin();
+    loop {
+        print!("Run which one? ");
+        io::stdout().flush().map_err(|err| err.to_string())?;
+        let mut line = String::new();
+        if stdin
+            .lock()
+            .read_line(&mut line)
+            .map_err(|err| err.to_string())?
+            == 0
+        {
+            return Err("No experiment picked".to_string());
+        }
+        let choice = line.trim();
+        let experiment = match choice.parse::<usize>() {
+            Ok(i) => EXPERIMENTS
+                .get(i.wrapping_sub(1))
+                .ok_or_else(|| format!("There's no experiment {}", i)),
+            Err(_) => find(choice),
+        };
+        match experiment {
+            Ok(experiment) => return Ok(experiment),
+            Err(err) => println!("{}", err),
+        }
+    }
 }